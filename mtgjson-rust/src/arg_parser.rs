@@ -0,0 +1,813 @@
+use pyo3::prelude::*;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::env;
+
+use crate::builders::compressor::CompressionFormat;
+use crate::builders::set_builder::{CardSortOrder, EnrichmentStep};
+
+/// MTGJSON build arguments - Rust port of `mtgjson5.arg_parser`
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[pyclass(name = "MtgjsonArgs")]
+pub struct MtgjsonArgs {
+    #[pyo3(get, set)]
+    pub use_envvars: bool,
+
+    #[pyo3(get, set)]
+    pub sets: Vec<String>,
+
+    #[pyo3(get, set)]
+    pub all_sets: bool,
+
+    #[pyo3(get, set)]
+    pub full_build: bool,
+
+    #[pyo3(get, set)]
+    pub resume_build: bool,
+
+    /// Skip every set that sorts before this code
+    #[pyo3(get, set)]
+    pub resume_from_set: Option<String>,
+
+    /// Only write per-set files, not the monolithic AllPrintings.json
+    #[pyo3(get, set)]
+    pub no_monolith: bool,
+
+    /// Leave the compiled outputs out of a full build, keeping prices
+    #[pyo3(get, set)]
+    pub skip_compiled: bool,
+
+    /// Leave the per-format AllPrintings and atomic card files out of a
+    /// full build
+    #[pyo3(get, set)]
+    pub skip_alt_formats: bool,
+
+    /// Write object keys alphabetically to match published MTGJSON files
+    #[pyo3(get, set)]
+    pub sort_keys: bool,
+
+    /// Order each set's cards are written in (number, name or mv);
+    /// defaults to collector number
+    #[pyo3(get, set)]
+    pub sort_cards_by: Option<String>,
+
+    #[pyo3(get, set)]
+    pub compress: bool,
+
+    /// Formats `--compress` writes (gz, bz2, xz, zip); empty means all
+    #[pyo3(get, set)]
+    pub compress_formats: Vec<String>,
+
+    #[pyo3(get, set)]
+    pub pretty: bool,
+
+    /// Spaces per level of pretty output (default 2)
+    #[pyo3(get, set)]
+    pub indent: Option<usize>,
+
+    /// Write each set both minified and pretty (`<CODE>.pretty.json`).
+    /// Takes precedence over `pretty` for set files.
+    #[pyo3(get, set)]
+    pub emit_both: bool,
+
+    #[pyo3(get, set)]
+    pub skip_sets: Vec<String>,
+
+    /// Scryfall set code -> code the set is written out as
+    #[pyo3(get, set)]
+    pub set_code_remap: HashMap<String, String>,
+
+    /// Set code -> JSON file of hand-added Scryfall-shaped cards to build
+    /// and merge into that set
+    #[pyo3(get, set)]
+    pub additional_cards_files: HashMap<String, String>,
+
+    /// Invent EDHREC ranks for cards EDHREC has no data for
+    #[pyo3(get, set)]
+    pub synthetic_edhrec: bool,
+
+    /// Enrichment steps to leave out (gatherer, edhrec, foreign,
+    /// multiversebridge)
+    #[pyo3(get, set)]
+    pub skip_enrichment: Vec<String>,
+
+    /// Write build_manifest.json describing everything that was built
+    #[pyo3(get, set)]
+    pub write_manifest: bool,
+
+    /// Abort when two cards or tokens in a set share a UUID
+    #[pyo3(get, set)]
+    pub strict_uuid: bool,
+
+    #[pyo3(get, set)]
+    pub price_build: bool,
+
+    /// Build price outputs from this existing AllPrices archive instead of
+    /// fetching live prices
+    #[pyo3(get, set)]
+    pub prices_input: Option<String>,
+
+    /// Embed each card's latest prices from the `prices_input` archive in
+    /// its `prices` field, for consumers of the legacy card layout
+    #[pyo3(get, set)]
+    pub include_prices_in_cards: bool,
+
+    /// Only keep price datapoints dated on or after this `YYYY-MM-DD` date
+    #[pyo3(get, set)]
+    pub since: Option<String>,
+
+    #[pyo3(get, set)]
+    pub referrals: bool,
+
+    #[pyo3(get, set)]
+    pub no_alerts: bool,
+
+    /// Don't print the build summary at the end
+    #[pyo3(get, set)]
+    pub quiet: bool,
+
+    /// Retries for a failed provider request (default 3)
+    #[pyo3(get, set)]
+    pub max_retries: Option<u32>,
+
+    /// Backoff before the first retry in milliseconds, doubled for each
+    /// retry after (default 500)
+    #[pyo3(get, set)]
+    pub retry_base_ms: Option<u64>,
+
+    /// Write every raw provider response body under this directory
+    #[pyo3(get, set)]
+    pub debug_dump_responses: Option<String>,
+
+    /// Always download from Scryfall instead of reusing cached responses
+    #[pyo3(get, set)]
+    pub no_cache: bool,
+
+    /// Seconds a cached Scryfall response is reused for (default 3600)
+    #[pyo3(get, set)]
+    pub cache_ttl: Option<u64>,
+
+    /// Load provider credentials from this config file instead of the
+    /// default `mtgjson.properties`
+    #[pyo3(get, set)]
+    pub providers_config: Option<String>,
+
+    #[pyo3(get, set)]
+    pub aws_ssm_download_config: Option<String>,
+
+    #[pyo3(get, set)]
+    pub aws_s3_upload_bucket: Option<String>,
+
+    /// Key prefix uploads are placed under in the bucket
+    #[pyo3(get, set)]
+    pub aws_s3_upload_prefix: Option<String>,
+
+    /// Log the uploads that would be made instead of uploading
+    #[pyo3(get, set)]
+    pub aws_s3_dry_run: bool,
+}
+
+#[pymethods]
+impl MtgjsonArgs {
+    #[new]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Code a set should be written out as, honoring --set-code-remap
+    pub fn get_output_set_code(&self, set_code: &str) -> String {
+        let set_code = set_code.to_uppercase();
+        self.set_code_remap
+            .get(&set_code)
+            .cloned()
+            .unwrap_or(set_code)
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+impl MtgjsonArgs {
+    /// Formats selected with --compress-formats, defaulting to all of them
+    pub fn get_compression_formats(&self) -> PyResult<Vec<CompressionFormat>> {
+        if self.compress_formats.is_empty() {
+            return Ok(CompressionFormat::ALL.to_vec());
+        }
+        self.compress_formats
+            .iter()
+            .map(|name| {
+                CompressionFormat::from_name(name).ok_or_else(|| {
+                    pyo3::exceptions::PyValueError::new_err(format!(
+                        "Invalid --compress-formats value '{}', expected gz, bz2, xz or zip",
+                        name
+                    ))
+                })
+            })
+            .collect()
+    }
+
+    /// Card order selected with --sort-cards-by, defaulting to collector number
+    pub fn get_card_sort_order(&self) -> PyResult<CardSortOrder> {
+        match &self.sort_cards_by {
+            None => Ok(CardSortOrder::default()),
+            Some(name) => CardSortOrder::from_name(name).ok_or_else(|| {
+                pyo3::exceptions::PyValueError::new_err(format!(
+                    "Invalid --sort-cards-by value '{}', expected number, name or mv",
+                    name
+                ))
+            }),
+        }
+    }
+
+    /// Enrichment steps selected with --skip-enrichment; unknown names are
+    /// warned about and ignored
+    pub fn get_skipped_enrichment(&self) -> Vec<EnrichmentStep> {
+        EnrichmentStep::parse_list(&self.skip_enrichment)
+    }
+}
+
+/// Parse a single `SET=value` entry of `flag`, uppercasing the set code
+fn parse_set_code_entry(entry: &str, flag: &str, expected: &str) -> PyResult<(String, String)> {
+    match entry.split_once('=') {
+        Some((set_code, value)) if !set_code.trim().is_empty() && !value.trim().is_empty() => {
+            Ok((set_code.trim().to_uppercase(), value.trim().to_string()))
+        }
+        _ => Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "Invalid {} value '{}', expected {}",
+            flag, entry, expected
+        ))),
+    }
+}
+
+/// Parse a single `OLD=NEW` remap entry
+fn parse_set_code_remap_entry(entry: &str) -> PyResult<(String, String)> {
+    parse_set_code_entry(entry, "--set-code-remap", "OLD=NEW")
+}
+
+/// Parse a single `SET=path` additional cards entry
+fn parse_additional_cards_file_entry(entry: &str) -> PyResult<(String, String)> {
+    parse_set_code_entry(entry, "--additional-cards-file", "SET=path")
+}
+
+/// Consume the values of a `nargs="*"` style flag
+fn take_flag_values(argv: &[String], index: &mut usize) -> Vec<String> {
+    let mut values = Vec::new();
+    while *index + 1 < argv.len() && !argv[*index + 1].starts_with('-') {
+        *index += 1;
+        values.push(argv[*index].to_uppercase());
+    }
+    values
+}
+
+/// Consume the single value of a flag
+fn take_flag_value(argv: &[String], index: &mut usize, flag: &str) -> PyResult<String> {
+    if *index + 1 >= argv.len() {
+        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "Argument {} expected a value",
+            flag
+        )));
+    }
+    *index += 1;
+    Ok(argv[*index].clone())
+}
+
+/// Parse a numeric flag or environment value
+fn parse_number<T: std::str::FromStr>(value: &str, flag: &str) -> PyResult<T> {
+    value.trim().parse().map_err(|_| {
+        pyo3::exceptions::PyValueError::new_err(format!(
+            "Invalid {} value '{}', expected a non-negative integer",
+            flag, value
+        ))
+    })
+}
+
+/// Validate a `YYYY-MM-DD` date given for `flag`
+fn parse_date(value: &str, flag: &str) -> PyResult<String> {
+    let value = value.trim();
+    chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map(|_| value.to_string())
+        .map_err(|_| {
+            pyo3::exceptions::PyValueError::new_err(format!(
+                "Invalid {} value '{}', expected a YYYY-MM-DD date",
+                flag, value
+            ))
+        })
+}
+
+/// Parse command line arguments from user to determine how to spawn up
+/// MTGJSON and complete the request. `argv` excludes the program name.
+#[pyfunction]
+pub fn parse_args(argv: Vec<String>) -> PyResult<MtgjsonArgs> {
+    let mut parsed_args = MtgjsonArgs::new();
+
+    let mut index = 0;
+    while index < argv.len() {
+        let flag = argv[index].as_str();
+        match flag {
+            "--use-envvars" => parsed_args.use_envvars = true,
+            "--sets" | "-s" => parsed_args.sets = take_flag_values(&argv, &mut index),
+            "--all-sets" | "-a" => parsed_args.all_sets = true,
+            "--full-build" | "-c" => parsed_args.full_build = true,
+            "--resume-build" | "-x" => parsed_args.resume_build = true,
+            "--resume-from-set" => {
+                parsed_args.resume_from_set =
+                    Some(take_flag_value(&argv, &mut index, flag)?.to_uppercase())
+            }
+            "--no-monolith" => parsed_args.no_monolith = true,
+            "--skip-compiled" => parsed_args.skip_compiled = true,
+            "--skip-alt-formats" => parsed_args.skip_alt_formats = true,
+            "--sort-keys" => parsed_args.sort_keys = true,
+            "--sort-cards-by" => {
+                parsed_args.sort_cards_by = Some(take_flag_value(&argv, &mut index, flag)?)
+            }
+            "--compress" | "-z" => parsed_args.compress = true,
+            "--compress-formats" => {
+                parsed_args.compress_formats = take_flag_values(&argv, &mut index)
+            }
+            "--pretty" | "-p" => parsed_args.pretty = true,
+            "--indent" => {
+                parsed_args.indent = Some(parse_number(
+                    &take_flag_value(&argv, &mut index, flag)?,
+                    flag,
+                )?)
+            }
+            "--emit-both" => parsed_args.emit_both = true,
+            "--skip-sets" | "-SS" => parsed_args.skip_sets = take_flag_values(&argv, &mut index),
+            "--set-code-remap" => {
+                let (old, new) =
+                    parse_set_code_remap_entry(&take_flag_value(&argv, &mut index, flag)?)?;
+                parsed_args.set_code_remap.insert(old, new);
+            }
+            "--additional-cards-file" => {
+                let (set_code, path) =
+                    parse_additional_cards_file_entry(&take_flag_value(&argv, &mut index, flag)?)?;
+                parsed_args.additional_cards_files.insert(set_code, path);
+            }
+            "--synthetic-edhrec" => parsed_args.synthetic_edhrec = true,
+            "--skip-enrichment" => {
+                parsed_args.skip_enrichment = take_flag_value(&argv, &mut index, flag)?
+                    .split(',')
+                    .map(|name| name.trim().to_lowercase())
+                    .filter(|name| !name.is_empty())
+                    .collect()
+            }
+            "--write-manifest" => parsed_args.write_manifest = true,
+            "--strict-uuid" => parsed_args.strict_uuid = true,
+            "--price-build" | "-PB" => parsed_args.price_build = true,
+            "--prices-input" => {
+                parsed_args.prices_input = Some(take_flag_value(&argv, &mut index, flag)?)
+            }
+            "--include-prices-in-cards" => parsed_args.include_prices_in_cards = true,
+            "--since" => {
+                parsed_args.since = Some(parse_date(
+                    &take_flag_value(&argv, &mut index, flag)?,
+                    flag,
+                )?)
+            }
+            "--referrals" | "-R" => parsed_args.referrals = true,
+            "--no-alerts" | "-NA" => parsed_args.no_alerts = true,
+            "--quiet" | "-q" => parsed_args.quiet = true,
+            "--max-retries" => {
+                parsed_args.max_retries = Some(parse_number(
+                    &take_flag_value(&argv, &mut index, flag)?,
+                    flag,
+                )?)
+            }
+            "--retry-base-ms" => {
+                parsed_args.retry_base_ms = Some(parse_number(
+                    &take_flag_value(&argv, &mut index, flag)?,
+                    flag,
+                )?)
+            }
+            "--debug-dump-responses" => {
+                parsed_args.debug_dump_responses = Some(take_flag_value(&argv, &mut index, flag)?)
+            }
+            "--no-cache" => parsed_args.no_cache = true,
+            "--cache-ttl" => {
+                parsed_args.cache_ttl = Some(parse_number(
+                    &take_flag_value(&argv, &mut index, flag)?,
+                    flag,
+                )?)
+            }
+            "--providers-config" => {
+                parsed_args.providers_config = Some(take_flag_value(&argv, &mut index, flag)?)
+            }
+            "--aws-ssm-download-config" => {
+                parsed_args.aws_ssm_download_config =
+                    Some(take_flag_value(&argv, &mut index, flag)?)
+            }
+            "--aws-s3-upload-bucket" => {
+                parsed_args.aws_s3_upload_bucket = Some(take_flag_value(&argv, &mut index, flag)?)
+            }
+            "--aws-s3-upload-prefix" => {
+                parsed_args.aws_s3_upload_prefix = Some(take_flag_value(&argv, &mut index, flag)?)
+            }
+            "--aws-s3-dry-run" => parsed_args.aws_s3_dry_run = true,
+            _ => {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "Unrecognized argument: {}",
+                    flag
+                )))
+            }
+        }
+        index += 1;
+    }
+
+    if !parsed_args.sets.is_empty() && parsed_args.all_sets {
+        return Err(pyo3::exceptions::PyValueError::new_err(
+            "Argument --all-sets not allowed with argument --sets",
+        ));
+    }
+
+    if parsed_args.use_envvars {
+        println!("Using environment variables over parser flags");
+        apply_environment_overrides(&mut parsed_args)?;
+    }
+
+    if parsed_args.include_prices_in_cards && parsed_args.prices_input.is_none() {
+        return Err(pyo3::exceptions::PyValueError::new_err(
+            "Argument --include-prices-in-cards requires argument --prices-input",
+        ));
+    }
+
+    parsed_args.get_compression_formats()?;
+    parsed_args.get_card_sort_order()?;
+
+    Ok(parsed_args)
+}
+
+/// Split a comma separated environment variable into its entries
+fn env_list(key: &str) -> Vec<String> {
+    env::var(key)
+        .unwrap_or_default()
+        .split(',')
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string())
+        .collect()
+}
+
+/// Environment variables are truthy when set to any non-empty value
+fn env_flag(key: &str) -> bool {
+    env::var(key).map(|v| !v.is_empty()).unwrap_or(false)
+}
+
+/// Override parsed flags with their environment variable equivalents
+fn apply_environment_overrides(parsed_args: &mut MtgjsonArgs) -> PyResult<()> {
+    parsed_args.sets = env_list("SETS");
+    parsed_args.all_sets = env_flag("ALL_SETS");
+    parsed_args.full_build = env_flag("FULL_BUILD");
+    parsed_args.resume_build = env_flag("RESUME_BUILD");
+    parsed_args.resume_from_set = env::var("RESUME_FROM_SET")
+        .ok()
+        .filter(|code| !code.is_empty())
+        .map(|code| code.to_uppercase());
+    parsed_args.no_monolith = env_flag("NO_MONOLITH");
+    parsed_args.skip_compiled = env_flag("SKIP_COMPILED");
+    parsed_args.skip_alt_formats = env_flag("SKIP_ALT_FORMATS");
+    parsed_args.sort_keys = env_flag("SORT_KEYS");
+    parsed_args.sort_cards_by = env::var("SORT_CARDS_BY")
+        .ok()
+        .filter(|order| !order.is_empty());
+    parsed_args.compress = env_flag("COMPRESS");
+    parsed_args.compress_formats = env_list("COMPRESS_FORMATS");
+    parsed_args.pretty = env_flag("PRETTY");
+    parsed_args.indent = env::var("INDENT")
+        .ok()
+        .filter(|value| !value.is_empty())
+        .map(|value| parse_number(&value, "INDENT"))
+        .transpose()?;
+    parsed_args.emit_both = env_flag("EMIT_BOTH");
+    parsed_args.skip_sets = env_list("SKIP_SETS");
+    parsed_args.set_code_remap = env_list("SET_CODE_REMAP")
+        .iter()
+        .map(|entry| parse_set_code_remap_entry(entry))
+        .collect::<PyResult<HashMap<String, String>>>()?;
+    parsed_args.additional_cards_files = env_list("ADDITIONAL_CARDS_FILES")
+        .iter()
+        .map(|entry| parse_additional_cards_file_entry(entry))
+        .collect::<PyResult<HashMap<String, String>>>()?;
+    parsed_args.synthetic_edhrec = env_flag("SYNTHETIC_EDHREC");
+    parsed_args.skip_enrichment = env_list("SKIP_ENRICHMENT");
+    parsed_args.write_manifest = env_flag("WRITE_MANIFEST");
+    parsed_args.strict_uuid = env_flag("STRICT_UUID");
+    parsed_args.price_build = env_flag("PRICE_BUILD");
+    parsed_args.prices_input = env::var("PRICES_INPUT")
+        .ok()
+        .filter(|path| !path.is_empty());
+    parsed_args.include_prices_in_cards = env_flag("INCLUDE_PRICES_IN_CARDS");
+    parsed_args.since = env::var("SINCE")
+        .ok()
+        .filter(|value| !value.is_empty())
+        .map(|value| parse_date(&value, "SINCE"))
+        .transpose()?;
+    parsed_args.referrals = env_flag("REFERRALS");
+    parsed_args.no_alerts = env_flag("NO_ALERTS");
+    parsed_args.quiet = env_flag("QUIET");
+    parsed_args.max_retries = env::var("MAX_RETRIES")
+        .ok()
+        .filter(|value| !value.is_empty())
+        .map(|value| parse_number(&value, "MAX_RETRIES"))
+        .transpose()?;
+    parsed_args.retry_base_ms = env::var("RETRY_BASE_MS")
+        .ok()
+        .filter(|value| !value.is_empty())
+        .map(|value| parse_number(&value, "RETRY_BASE_MS"))
+        .transpose()?;
+    parsed_args.debug_dump_responses = env::var("DEBUG_DUMP_RESPONSES")
+        .ok()
+        .filter(|dir| !dir.is_empty());
+    parsed_args.no_cache = env_flag("NO_CACHE");
+    parsed_args.cache_ttl = env::var("CACHE_TTL")
+        .ok()
+        .filter(|value| !value.is_empty())
+        .map(|value| parse_number(&value, "CACHE_TTL"))
+        .transpose()?;
+    parsed_args.providers_config = env::var("PROVIDERS_CONFIG")
+        .ok()
+        .filter(|path| !path.is_empty());
+    parsed_args.aws_ssm_download_config = env::var("AWS_SSM_DOWNLOAD_CONFIG").ok();
+    parsed_args.aws_s3_upload_bucket = env::var("AWS_S3_UPLOAD_BUCKET").ok();
+    parsed_args.aws_s3_upload_prefix = env::var("AWS_S3_UPLOAD_PREFIX")
+        .ok()
+        .filter(|prefix| !prefix.is_empty());
+    parsed_args.aws_s3_dry_run = env_flag("AWS_S3_DRY_RUN");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn argv(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_sets_are_uppercased() {
+        let args = parse_args(argv(&["--sets", "dom", "m21", "-p"])).unwrap();
+        assert_eq!(args.sets, vec!["DOM", "M21"]);
+        assert!(args.pretty);
+        assert!(!args.all_sets);
+        assert!(!args.synthetic_edhrec);
+    }
+
+    #[test]
+    fn test_parse_synthetic_edhrec() {
+        let args = parse_args(argv(&["-s", "C21", "--synthetic-edhrec"])).unwrap();
+        assert!(args.synthetic_edhrec);
+    }
+
+    #[test]
+    fn test_parse_skip_enrichment() {
+        let args = parse_args(argv(&[
+            "-s",
+            "C21",
+            "--skip-enrichment",
+            "Gatherer, edhrec",
+        ]))
+        .unwrap();
+        assert_eq!(args.skip_enrichment, vec!["gatherer", "edhrec"]);
+        assert_eq!(
+            args.get_skipped_enrichment(),
+            vec![EnrichmentStep::Gatherer, EnrichmentStep::Edhrec]
+        );
+    }
+
+    #[test]
+    fn test_parse_set_code_remap_repeatable() {
+        let args = parse_args(argv(&[
+            "-s",
+            "pDOM",
+            "--set-code-remap",
+            "pDOM=PDOM",
+            "--set-code-remap",
+            "m21=M21X",
+        ]))
+        .unwrap();
+        assert_eq!(args.set_code_remap.len(), 2);
+        assert_eq!(args.get_output_set_code("pdom"), "PDOM");
+        assert_eq!(args.get_output_set_code("M21"), "M21X");
+        assert_eq!(args.get_output_set_code("KHM"), "KHM");
+    }
+
+    #[test]
+    fn test_parse_additional_cards_file() {
+        let args = parse_args(argv(&[
+            "-s",
+            "TST",
+            "--additional-cards-file",
+            "tst=extra/TST.json",
+        ]))
+        .unwrap();
+        assert_eq!(
+            args.additional_cards_files.get("TST").map(String::as_str),
+            Some("extra/TST.json")
+        );
+        assert!(parse_args(argv(&["--additional-cards-file", "extra/TST.json"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_set_code_remap_invalid() {
+        assert!(parse_args(argv(&["--set-code-remap", "PDOM"])).is_err());
+        assert!(parse_args(argv(&["--set-code-remap"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_emit_both_with_pretty() {
+        let args = parse_args(argv(&["-s", "C21", "--emit-both", "--pretty"])).unwrap();
+        assert!(args.emit_both);
+        assert!(args.pretty);
+    }
+
+    #[test]
+    fn test_parse_resume_from_set() {
+        let args = parse_args(argv(&["-a", "--resume-from-set", "mh2"])).unwrap();
+        assert_eq!(args.resume_from_set.as_deref(), Some("MH2"));
+        assert!(parse_args(argv(&["--resume-from-set"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_debug_dump_responses() {
+        let args = parse_args(argv(&["-s", "DOM"])).unwrap();
+        assert_eq!(args.debug_dump_responses, None);
+
+        let args = parse_args(argv(&["--debug-dump-responses", "/tmp/dumps"])).unwrap();
+        assert_eq!(args.debug_dump_responses.as_deref(), Some("/tmp/dumps"));
+    }
+
+    #[test]
+    fn test_parse_cache_settings() {
+        let args = parse_args(argv(&["-s", "DOM"])).unwrap();
+        assert!(!args.no_cache);
+        assert_eq!(args.cache_ttl, None);
+
+        let args = parse_args(argv(&["--no-cache", "--cache-ttl", "600"])).unwrap();
+        assert!(args.no_cache);
+        assert_eq!(args.cache_ttl, Some(600));
+
+        assert!(parse_args(argv(&["--cache-ttl", "forever"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_compress_formats() {
+        let args = parse_args(argv(&["-z"])).unwrap();
+        assert_eq!(
+            args.get_compression_formats().unwrap(),
+            CompressionFormat::ALL.to_vec()
+        );
+
+        let args = parse_args(argv(&["-z", "--compress-formats", "gz", "xz"])).unwrap();
+        assert_eq!(
+            args.get_compression_formats().unwrap(),
+            vec![CompressionFormat::Gzip, CompressionFormat::Xz]
+        );
+
+        assert!(parse_args(argv(&["-z", "--compress-formats", "rar"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_aws_s3_upload() {
+        let args = parse_args(argv(&[
+            "--aws-s3-upload-bucket",
+            "mtgjson",
+            "--aws-s3-upload-prefix",
+            "api/v5",
+            "--aws-s3-dry-run",
+        ]))
+        .unwrap();
+        assert_eq!(args.aws_s3_upload_bucket.as_deref(), Some("mtgjson"));
+        assert_eq!(args.aws_s3_upload_prefix.as_deref(), Some("api/v5"));
+        assert!(args.aws_s3_dry_run);
+    }
+
+    #[test]
+    fn test_parse_include_prices_in_cards() {
+        let args = parse_args(argv(&[
+            "--prices-input",
+            "AllPrices.json",
+            "--include-prices-in-cards",
+        ]))
+        .unwrap();
+        assert!(args.include_prices_in_cards);
+        assert!(parse_args(argv(&["--include-prices-in-cards"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_sort_keys() {
+        assert!(!parse_args(argv(&["-s", "DOM"])).unwrap().sort_keys);
+        assert!(
+            parse_args(argv(&["-s", "DOM", "--sort-keys"]))
+                .unwrap()
+                .sort_keys
+        );
+    }
+
+    #[test]
+    fn test_parse_quiet() {
+        assert!(!parse_args(argv(&["-s", "DOM"])).unwrap().quiet);
+        assert!(parse_args(argv(&["-s", "DOM", "--quiet"])).unwrap().quiet);
+    }
+
+    #[test]
+    fn test_parse_strict_uuid() {
+        assert!(!parse_args(argv(&["-s", "DOM"])).unwrap().strict_uuid);
+        assert!(
+            parse_args(argv(&["-s", "DOM", "--strict-uuid"]))
+                .unwrap()
+                .strict_uuid
+        );
+    }
+
+    #[test]
+    fn test_parse_prices_input() {
+        let args = parse_args(argv(&["--prices-input", "AllPrices.json"])).unwrap();
+        assert_eq!(args.prices_input.as_deref(), Some("AllPrices.json"));
+        assert!(parse_args(argv(&["--prices-input"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_providers_config() {
+        let args = parse_args(argv(&[
+            "--providers-config",
+            "/etc/mtgjson/staging.properties",
+        ]))
+        .unwrap();
+        assert_eq!(
+            args.providers_config.as_deref(),
+            Some("/etc/mtgjson/staging.properties")
+        );
+        assert!(parse_args(argv(&["--providers-config"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_indent() {
+        let args = parse_args(argv(&["-p"])).unwrap();
+        assert_eq!(args.indent, None);
+
+        let args = parse_args(argv(&["-p", "--indent", "4"])).unwrap();
+        assert_eq!(args.indent, Some(4));
+        assert!(parse_args(argv(&["--indent", "wide"])).is_err());
+        assert!(parse_args(argv(&["--indent"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_skip_compiled_steps() {
+        let args = parse_args(argv(&["--full-build", "--skip-compiled"])).unwrap();
+        assert!(args.full_build);
+        assert!(args.skip_compiled);
+        assert!(!args.skip_alt_formats);
+
+        let args = parse_args(argv(&["-c", "--skip-alt-formats"])).unwrap();
+        assert!(!args.skip_compiled);
+        assert!(args.skip_alt_formats);
+    }
+
+    #[test]
+    fn test_parse_sort_cards_by() {
+        let args = parse_args(argv(&["-s", "DOM"])).unwrap();
+        assert_eq!(args.get_card_sort_order().unwrap(), CardSortOrder::Number);
+
+        let args = parse_args(argv(&["--sort-cards-by", "name"])).unwrap();
+        assert_eq!(args.sort_cards_by.as_deref(), Some("name"));
+        assert_eq!(args.get_card_sort_order().unwrap(), CardSortOrder::Name);
+
+        let args = parse_args(argv(&["--sort-cards-by", "MV"])).unwrap();
+        assert_eq!(
+            args.get_card_sort_order().unwrap(),
+            CardSortOrder::ManaValue
+        );
+
+        assert!(parse_args(argv(&["--sort-cards-by", "rarity"])).is_err());
+        assert!(parse_args(argv(&["--sort-cards-by"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_since() {
+        let args = parse_args(argv(&["--prices-input", "AllPrices.json"])).unwrap();
+        assert_eq!(args.since, None);
+
+        let args = parse_args(argv(&["--since", "2024-03-01"])).unwrap();
+        assert_eq!(args.since.as_deref(), Some("2024-03-01"));
+        assert!(parse_args(argv(&["--since", "03/01/2024"])).is_err());
+        assert!(parse_args(argv(&["--since"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_retry_settings() {
+        let args = parse_args(argv(&["-s", "DOM"])).unwrap();
+        assert_eq!(args.max_retries, None);
+        assert_eq!(args.retry_base_ms, None);
+
+        let args = parse_args(argv(&["--max-retries", "5", "--retry-base-ms", "250"])).unwrap();
+        assert_eq!(args.max_retries, Some(5));
+        assert_eq!(args.retry_base_ms, Some(250));
+
+        assert!(parse_args(argv(&["--max-retries", "many"])).is_err());
+    }
+
+    #[test]
+    fn test_sets_and_all_sets_are_exclusive() {
+        assert!(parse_args(argv(&["--sets", "DOM", "--all-sets"])).is_err());
+    }
+}
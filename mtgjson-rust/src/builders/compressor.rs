@@ -0,0 +1,217 @@
+// MTGJSON output compression - Rust port of `mtgjson5.compress_generator`
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// Archive formats each output file is distributed in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressionFormat {
+    Gzip,
+    Bzip2,
+    Xz,
+    Zip,
+}
+
+impl CompressionFormat {
+    /// Every format, matching the MTGJSON distribution layout
+    pub const ALL: [CompressionFormat; 4] = [
+        CompressionFormat::Gzip,
+        CompressionFormat::Bzip2,
+        CompressionFormat::Xz,
+        CompressionFormat::Zip,
+    ];
+
+    /// Extension appended to the original file name
+    pub fn extension(&self) -> &'static str {
+        match self {
+            CompressionFormat::Gzip => "gz",
+            CompressionFormat::Bzip2 => "bz2",
+            CompressionFormat::Xz => "xz",
+            CompressionFormat::Zip => "zip",
+        }
+    }
+
+    /// Look up a format by its extension or name, e.g. `gz` or `gzip`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "gz" | "gzip" => Some(CompressionFormat::Gzip),
+            "bz2" | "bzip2" => Some(CompressionFormat::Bzip2),
+            "xz" => Some(CompressionFormat::Xz),
+            "zip" => Some(CompressionFormat::Zip),
+            _ => None,
+        }
+    }
+
+    /// Path the compressed copy of `path` is written to
+    pub fn output_path(&self, path: &Path) -> PathBuf {
+        let mut file_name = path.as_os_str().to_owned();
+        file_name.push(".");
+        file_name.push(self.extension());
+        PathBuf::from(file_name)
+    }
+}
+
+/// Compress a single file into `format`, leaving the original in place
+pub fn compress_file(path: &Path, format: CompressionFormat) -> io::Result<PathBuf> {
+    let output_path = format.output_path(path);
+    let mut reader = BufReader::new(File::open(path)?);
+    let writer = BufWriter::new(File::create(&output_path)?);
+
+    match format {
+        CompressionFormat::Gzip => {
+            let mut encoder = flate2::write::GzEncoder::new(writer, flate2::Compression::default());
+            io::copy(&mut reader, &mut encoder)?;
+            encoder.finish()?.flush()?;
+        }
+        CompressionFormat::Bzip2 => {
+            let mut encoder = bzip2::write::BzEncoder::new(writer, bzip2::Compression::default());
+            io::copy(&mut reader, &mut encoder)?;
+            encoder.finish()?.flush()?;
+        }
+        CompressionFormat::Xz => {
+            let mut encoder = xz2::write::XzEncoder::new(writer, 6);
+            io::copy(&mut reader, &mut encoder)?;
+            encoder.finish()?.flush()?;
+        }
+        CompressionFormat::Zip => {
+            let entry_name = path
+                .file_name()
+                .and_then(|s| s.to_str())
+                .unwrap_or_default();
+            let options = zip::write::SimpleFileOptions::default()
+                .compression_method(zip::CompressionMethod::Deflated);
+            let mut zip_writer = zip::ZipWriter::new(writer);
+            zip_writer.start_file(entry_name, options)?;
+            io::copy(&mut reader, &mut zip_writer)?;
+            zip_writer.finish()?.flush()?;
+        }
+    }
+
+    Ok(output_path)
+}
+
+/// A compressed copy is up to date when it is at least as new as the original,
+/// which lets a resume build skip work it already did
+fn is_up_to_date(path: &Path, output_path: &Path) -> bool {
+    let modified = |p: &Path| fs::metadata(p).and_then(|m| m.modified()).ok();
+    match (modified(path), modified(output_path)) {
+        (Some(original), Some(compressed)) => compressed >= original,
+        _ => false,
+    }
+}
+
+/// Compress every `.json` file in `output_dir` into each of `formats`,
+/// writing the archives next to the originals
+pub fn compress_output(output_dir: &Path, formats: &[CompressionFormat]) -> io::Result<()> {
+    let mut json_paths: Vec<PathBuf> = fs::read_dir(output_dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("json"))
+        .collect();
+    json_paths.sort();
+
+    println!(
+        "Compressing {} output files in {:?}",
+        json_paths.len(),
+        output_dir
+    );
+    for path in &json_paths {
+        for format in formats {
+            if is_up_to_date(path, &format.output_path(path)) {
+                continue;
+            }
+            compress_file(path, *format)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    fn decompress(path: &Path, format: CompressionFormat) -> Vec<u8> {
+        let file = File::open(path).unwrap();
+        let mut contents = Vec::new();
+        match format {
+            CompressionFormat::Gzip => {
+                flate2::read::GzDecoder::new(file)
+                    .read_to_end(&mut contents)
+                    .unwrap();
+            }
+            CompressionFormat::Bzip2 => {
+                bzip2::read::BzDecoder::new(file)
+                    .read_to_end(&mut contents)
+                    .unwrap();
+            }
+            CompressionFormat::Xz => {
+                xz2::read::XzDecoder::new(file)
+                    .read_to_end(&mut contents)
+                    .unwrap();
+            }
+            CompressionFormat::Zip => {
+                let mut archive = zip::ZipArchive::new(file).unwrap();
+                let mut entry = archive.by_index(0).unwrap();
+                assert_eq!(entry.name(), "TST.json");
+                entry.read_to_end(&mut contents).unwrap();
+            }
+        }
+        contents
+    }
+
+    #[test]
+    fn test_compress_output_round_trips_each_format() {
+        let output_dir = std::env::temp_dir().join("mtgjson_compressor_test");
+        let _ = fs::remove_dir_all(&output_dir);
+        fs::create_dir_all(&output_dir).unwrap();
+
+        let original = br#"{"meta":{"version":"5.2.2"},"data":{"code":"TST","cards":[]}}"#;
+        let json_path = output_dir.join("TST.json");
+        fs::write(&json_path, original).unwrap();
+
+        compress_output(&output_dir, &CompressionFormat::ALL).unwrap();
+
+        assert_eq!(fs::read(&json_path).unwrap(), original);
+        for format in CompressionFormat::ALL {
+            let compressed_path = output_dir.join(format!("TST.json.{}", format.extension()));
+            assert_eq!(decompress(&compressed_path, format), original);
+        }
+
+        let _ = fs::remove_dir_all(&output_dir);
+    }
+
+    #[test]
+    fn test_compress_output_skips_up_to_date_archives() {
+        let output_dir = std::env::temp_dir().join("mtgjson_compressor_resume_test");
+        let _ = fs::remove_dir_all(&output_dir);
+        fs::create_dir_all(&output_dir).unwrap();
+
+        fs::write(output_dir.join("TST.json"), b"{}").unwrap();
+        compress_output(&output_dir, &[CompressionFormat::Gzip]).unwrap();
+        let gzip_path = output_dir.join("TST.json.gz");
+        let first_modified = fs::metadata(&gzip_path).unwrap().modified().unwrap();
+
+        compress_output(&output_dir, &[CompressionFormat::Gzip]).unwrap();
+        assert_eq!(
+            fs::metadata(&gzip_path).unwrap().modified().unwrap(),
+            first_modified
+        );
+        assert!(!output_dir.join("TST.json.gz.gz").exists());
+
+        let _ = fs::remove_dir_all(&output_dir);
+    }
+
+    #[test]
+    fn test_compression_format_from_name() {
+        assert_eq!(
+            CompressionFormat::from_name("GZ"),
+            Some(CompressionFormat::Gzip)
+        );
+        assert_eq!(
+            CompressionFormat::from_name("bzip2"),
+            Some(CompressionFormat::Bzip2)
+        );
+        assert_eq!(CompressionFormat::from_name("rar"), None);
+    }
+}
@@ -1,16 +1,19 @@
+pub mod compressor;
 pub mod output_generator;
 pub mod parallel_call;
 pub mod price_builder;
+pub mod resources;
+pub mod runtime;
 /// Builders module - Main module file for MTGJSON builders
 pub mod set_builder;
 pub mod set_builder_functions;
 
 // Re-export main types and functions for easier access
 pub use set_builder::{
-    add_leadership_skills, add_uuid_placeholder, build_base_mtgjson_cards, build_mtgjson_set,
-    enhance_cards_with_metadata, get_card_cmc, get_card_colors, get_translation_data, is_number,
-    mark_duel_decks, parse_card_types, parse_foreign, parse_keyrune_code, parse_legalities,
-    parse_printings, parse_rulings,
+    add_leadership_skills, add_uuid, add_uuid_placeholder, build_base_mtgjson_cards,
+    build_mtgjson_card, build_mtgjson_set, enhance_cards_with_metadata, get_card_cmc,
+    get_card_colors, get_translation_data, is_number, mark_duel_decks, parse_card_types,
+    parse_foreign, parse_keyrune_code, parse_legalities, parse_printings, parse_rulings,
 };
 
 pub use parallel_call::{
@@ -1,14 +1,32 @@
 // MTGJSON output generator - High performance file writing and JSON processing
 use pyo3::prelude::*;
 
+use serde::Serialize;
 use serde_json;
-use std::collections::HashMap;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::io::{BufWriter, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+use crate::builders::compressor::CompressionFormat;
+use crate::builders::runtime::block_on_shared;
+use crate::builders::set_builder::load_known_frame_effects;
 use crate::classes::meta::MtgjsonMetaObject;
 use crate::compiled_classes::*;
+use crate::providers::third_party::TCGPlayerProvider;
+
+/// Spaces per level of pretty output, MTGJSON's historical format
+pub const DEFAULT_INDENT: usize = 2;
+
+/// How an output file's JSON is laid out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputStyle {
+    /// A single line with no whitespace
+    Minified,
+    /// One value per line, nested `indent` spaces per level
+    Pretty { indent: usize },
+}
 
 #[pyclass(name = "OutputGenerator")]
 #[derive(Debug, Clone)]
@@ -19,6 +37,18 @@ pub struct OutputGenerator {
     pub output_date: String,
     pub output_files: Vec<String>,
     pub compression_enabled: bool,
+    /// Skip writing the monolithic AllPrintings.json
+    pub no_monolith: bool,
+    /// Skip the compiled outputs (AllPrintings, AtomicCards, SetList, ...)
+    /// of a full build; prices and Meta are still written
+    pub skip_compiled: bool,
+    /// Skip the per-format AllPrintings and atomic card files
+    pub skip_alt_formats: bool,
+    /// Emit every object's keys in alphabetical order, as published MTGJSON
+    /// files do, so outputs can be diffed byte for byte against upstream
+    pub sort_keys: bool,
+    /// Spaces per level when writing pretty output
+    pub indent: usize,
 }
 
 #[pymethods]
@@ -33,6 +63,11 @@ impl OutputGenerator {
             output_date: String::new(),
             output_files: Vec::new(),
             compression_enabled: true,
+            no_monolith: false,
+            skip_compiled: false,
+            skip_alt_formats: false,
+            sort_keys: false,
+            indent: DEFAULT_INDENT,
         }
     }
 
@@ -134,6 +169,36 @@ impl OutputGenerator {
         self.output_files.clone()
     }
 
+    #[getter]
+    pub fn get_no_monolith(&self) -> bool {
+        self.no_monolith
+    }
+
+    #[setter]
+    pub fn set_no_monolith(&mut self, no_monolith: bool) {
+        self.no_monolith = no_monolith;
+    }
+
+    #[getter]
+    pub fn get_skip_compiled(&self) -> bool {
+        self.skip_compiled
+    }
+
+    #[setter]
+    pub fn set_skip_compiled(&mut self, skip_compiled: bool) {
+        self.skip_compiled = skip_compiled;
+    }
+
+    #[getter]
+    pub fn get_skip_alt_formats(&self) -> bool {
+        self.skip_alt_formats
+    }
+
+    #[setter]
+    pub fn set_skip_alt_formats(&mut self, skip_alt_formats: bool) {
+        self.skip_alt_formats = skip_alt_formats;
+    }
+
     #[getter]
     pub fn get_compression_enabled(&self) -> bool {
         self.compression_enabled
@@ -283,38 +348,57 @@ impl OutputGenerator {
         // Set pretty print from parameter or use instance default
         let use_pretty_print = pretty_print.unwrap_or(self.pretty_print);
 
+        // Every compiled output is assembled from the set files already
+        // written, so read them into AllPrintings once
+        let mut all_printings = MtgjsonAllPrintings::from_path(self.output_path.clone())?;
+        all_printings.add_first_printings();
+
         // Generate all major outputs in parallel-friendly order
-        self.build_all_printings_files(use_pretty_print)?;
-        self.generate_compiled_prices_output(use_pretty_print)?;
-        self.build_compiled_list(use_pretty_print)?;
-        self.build_keywords(use_pretty_print)?;
-        self.build_card_types(use_pretty_print)?;
+        if !self.skip_compiled {
+            self.build_all_printings_files(&all_printings, use_pretty_print)?;
+        }
+        if !self.skip_alt_formats {
+            self.build_alt_format_files(&all_printings, use_pretty_print)?;
+        }
         self.build_meta(use_pretty_print)?;
-        self.build_set_list(use_pretty_print)?;
-        self.build_atomic_cards(use_pretty_print)?;
-        self.build_deck_list(use_pretty_print)?;
+        if self.skip_compiled {
+            return Ok(());
+        }
+        self.build_compiled_list(use_pretty_print)?;
+        self.build_keywords(&all_printings, use_pretty_print)?;
+        self.build_card_types(&all_printings, use_pretty_print)?;
+        self.build_set_list(&all_printings, use_pretty_print)?;
+        let atomic_cards = Self::build_atomic_cards(&all_printings);
+        self.write_compiled_output(
+            "AtomicCards",
+            &atomic_cards.atomic_cards_json(),
+            use_pretty_print,
+        )?;
+        self.build_deck_list(&all_printings, use_pretty_print)?;
+        self.build_tcgplayer_skus(&all_printings, use_pretty_print)?;
         self.build_enum_values(use_pretty_print)?;
+        self.build_all_tokens(use_pretty_print)?;
 
         Ok(())
     }
 
-    /// Build AllPrintings and related format files
-    pub fn build_all_printings_files(&self, pretty_print: bool) -> PyResult<()> {
-        // Generate AllPrintings
-        let all_printings = MtgjsonAllPrintings::new();
-        let all_printings_json = serde_json::to_string(&all_printings).map_err(|e| {
-            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Serialization error: {}", e))
-        })?;
-        self.create_compiled_output("AllPrintings", all_printings_json, pretty_print)?;
-
-        self.build_format_specific_files(&all_printings, pretty_print)?;
+    /// Build AllPrintings and AllIdentifiers
+    pub fn build_all_printings_files(
+        &self,
+        all_printings: &MtgjsonAllPrintings,
+        pretty_print: bool,
+    ) -> PyResult<()> {
+        if !self.no_monolith {
+            self.write_compiled_output("AllPrintings", &all_printings.all_sets_dict, pretty_print)?;
+        }
 
         // Generate AllIdentifiers
-        let all_identifiers = MtgjsonAllIdentifiers::new();
-        let all_identifiers_json = serde_json::to_string(&all_identifiers).map_err(|e| {
-            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Serialization error: {}", e))
-        })?;
-        self.create_compiled_output("AllIdentifiers", all_identifiers_json, pretty_print)?;
+        let all_identifiers = build_all_identifiers(all_printings);
+        self.write_compiled_output(
+            "AllIdentifiers",
+            &all_identifiers.all_identifiers_dict,
+            pretty_print,
+        )?;
 
         Ok(())
     }
@@ -330,29 +414,26 @@ impl OutputGenerator {
         for (format_name, _set_codes) in format_map {
             let format_data = self.filter_all_printings_by_format(all_printings, &format_name)?;
             let filename = format!("AllPrintings{}", format_name);
-            let format_data_json = serde_json::to_string(&format_data).map_err(|e| {
-                PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
-                    "Serialization error: {}",
-                    e
-                ))
-            })?;
-            self.create_compiled_output(&filename, format_data_json, pretty_print)?;
+            self.write_compiled_output(&filename, &format_data, pretty_print)?;
         }
 
         Ok(())
     }
 
-    /// Build atomic cards files
-    pub fn build_atomic_cards(&self, pretty_print: bool) -> PyResult<()> {
-        let atomic_cards = MtgjsonAtomicCards::new(None);
-        let atomic_cards_json = serde_json::to_string(&atomic_cards).map_err(|e| {
-            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Serialization error: {}", e))
-        })?;
-        self.create_compiled_output("AtomicCards", atomic_cards_json, pretty_print)?;
-
-        self.build_atomic_specific_files(pretty_print)?;
+    /// Build AtomicCards from every printing in AllPrintings
+    #[staticmethod]
+    pub fn build_atomic_cards(all_printings: &MtgjsonAllPrintings) -> MtgjsonAtomicCards {
+        MtgjsonAtomicCards::from_all_printings(all_printings)
+    }
 
-        Ok(())
+    /// Build the per-format AllPrintings and atomic card files
+    pub fn build_alt_format_files(
+        &self,
+        all_printings: &MtgjsonAllPrintings,
+        pretty_print: bool,
+    ) -> PyResult<()> {
+        self.build_format_specific_files(all_printings, pretty_print)?;
+        self.build_atomic_specific_files(pretty_print)
     }
 
     /// Build format-specific atomic cards
@@ -362,13 +443,7 @@ impl OutputGenerator {
         for (format_name, _cards) in card_format_map {
             let atomic_cards = MtgjsonAtomicCards::new(None);
             let filename = format!("{}Cards", format_name);
-            let atomic_cards_json = serde_json::to_string(&atomic_cards).map_err(|e| {
-                PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
-                    "Serialization error: {}",
-                    e
-                ))
-            })?;
-            self.create_compiled_output(&filename, atomic_cards_json, pretty_print)?;
+            self.write_compiled_output(&filename, &atomic_cards, pretty_print)?;
         }
 
         Ok(())
@@ -377,71 +452,131 @@ impl OutputGenerator {
     /// Generate compiled prices output
     pub fn generate_compiled_prices_output(&self, pretty_print: bool) -> PyResult<()> {
         let prices_data = HashMap::<String, serde_json::Value>::new();
-        let prices_data_json = serde_json::to_string(&prices_data).map_err(|e| {
-            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Serialization error: {}", e))
-        })?;
-
-        self.create_compiled_output("AllPrices", prices_data_json.clone(), pretty_print)?;
-        self.create_compiled_output("AllPricesToday", prices_data_json, pretty_print)?;
-
-        Ok(())
+        self.write_compiled_prices_output(&prices_data, &prices_data, pretty_print)
     }
 
     /// Build other compiled outputs
     pub fn build_compiled_list(&self, pretty_print: bool) -> PyResult<()> {
         let compiled_list = MtgjsonCompiledList::new();
-        let compiled_list_json = serde_json::to_string(&compiled_list).map_err(|e| {
-            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Serialization error: {}", e))
-        })?;
-        self.create_compiled_output("CompiledList", compiled_list_json, pretty_print)
+        self.write_compiled_output("CompiledList", &compiled_list, pretty_print)
     }
 
-    pub fn build_keywords(&self, pretty_print: bool) -> PyResult<()> {
-        let keywords = MtgjsonKeywords::new();
-        let keywords_json = serde_json::to_string(&keywords).map_err(|e| {
-            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Serialization error: {}", e))
-        })?;
-        self.create_compiled_output("Keywords", keywords_json, pretty_print)
+    /// Build Keywords from the keywords on cards in the set files already written
+    pub fn build_keywords(
+        &self,
+        all_printings: &MtgjsonAllPrintings,
+        pretty_print: bool,
+    ) -> PyResult<()> {
+        let keywords = MtgjsonKeywords::from_all_printings(all_printings);
+        self.write_compiled_output("Keywords", &keywords, pretty_print)
     }
 
-    pub fn build_card_types(&self, pretty_print: bool) -> PyResult<()> {
-        let card_types = MtgjsonCardTypesObject::new();
-        let card_types_json = serde_json::to_string(&card_types).map_err(|e| {
-            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Serialization error: {}", e))
-        })?;
-        self.create_compiled_output("CardTypes", card_types_json, pretty_print)
+    /// Build CardTypes from the type lines of the set files already written
+    pub fn build_card_types(
+        &self,
+        all_printings: &MtgjsonAllPrintings,
+        pretty_print: bool,
+    ) -> PyResult<()> {
+        let card_types = MtgjsonCardTypesObject::from_all_printings(all_printings);
+        self.write_compiled_output("CardTypes", &card_types.types, pretty_print)
     }
 
     pub fn build_meta(&self, pretty_print: bool) -> PyResult<()> {
         let meta = MtgjsonMetaObject::with_current_date(None);
-        let meta_json = serde_json::to_string(&meta).map_err(|e| {
-            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Serialization error: {}", e))
-        })?;
-        self.create_compiled_output("Meta", meta_json, pretty_print)
+        self.write_compiled_output("Meta", &meta, pretty_print)
     }
 
-    pub fn build_set_list(&self, pretty_print: bool) -> PyResult<()> {
-        let set_list = MtgjsonSetObjectList::new();
-        let set_list_json = serde_json::to_string(&set_list).map_err(|e| {
-            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Serialization error: {}", e))
-        })?;
-        self.create_compiled_output("SetList", set_list_json, pretty_print)
+    /// Build SetList from the set files already written
+    pub fn build_set_list(
+        &self,
+        all_printings: &MtgjsonAllPrintings,
+        pretty_print: bool,
+    ) -> PyResult<()> {
+        let set_list = MtgjsonSetObjectList::from_sets(all_printings.all_sets_dict.values());
+        self.write_compiled_output("SetList", &set_list.set_list, pretty_print)
     }
 
-    pub fn build_deck_list(&self, pretty_print: bool) -> PyResult<()> {
-        let deck_list = MtgjsonDeckObjectList::new(Vec::new());
-        let deck_list_json = serde_json::to_string(&deck_list).map_err(|e| {
-            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Serialization error: {}", e))
-        })?;
-        self.create_compiled_output("DeckList", deck_list_json, pretty_print)
+    /// Build DeckList from the decks of every set file already written
+    pub fn build_deck_list(
+        &self,
+        all_printings: &MtgjsonAllPrintings,
+        pretty_print: bool,
+    ) -> PyResult<()> {
+        let decks = all_printings
+            .all_sets_dict
+            .values()
+            .flat_map(|mtgjson_set| &mtgjson_set.decks);
+
+        let deck_list = MtgjsonDeckObjectList::from_deck_refs(decks);
+        self.write_compiled_output("DeckList", &deck_list.decks, pretty_print)
     }
 
+    /// Build TcgplayerSkus, the TCGPlayer SKUs of every card in AllPrintings
+    pub fn build_tcgplayer_skus(
+        &self,
+        all_printings: &MtgjsonAllPrintings,
+        pretty_print: bool,
+    ) -> PyResult<()> {
+        let cards = all_printings
+            .all_sets_dict
+            .values()
+            .flat_map(|mtgjson_set| &mtgjson_set.cards);
+
+        let tcgplayer_provider = TCGPlayerProvider::new()?;
+        match block_on_shared(tcgplayer_provider.build_tcgplayer_skus_async(cards)) {
+            Ok(tcgplayer_skus) => self.write_compiled_output(
+                "TcgplayerSkus",
+                &tcgplayer_skus.tcgplayer_skus,
+                pretty_print,
+            ),
+            Err(e) => {
+                eprintln!("Warning: Failed to build TcgplayerSkus: {}", e);
+                Ok(())
+            }
+        }
+    }
+
+    /// Build EnumValues from the values observed in every set file already
+    /// written, so it must run after the sets are built
     pub fn build_enum_values(&self, pretty_print: bool) -> PyResult<()> {
-        let enum_values = MtgjsonEnumValues::new();
-        let enum_values_json = serde_json::to_string(&enum_values).map_err(|e| {
-            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Serialization error: {}", e))
+        let mut enum_values = MtgjsonEnumValues::new();
+        self.for_each_set_file(|_, set_data| enum_values.add_set(set_data))?;
+
+        let known_frame_effects = load_known_frame_effects();
+        if !known_frame_effects.is_empty() {
+            if let Some(warning) = enum_values.unknown_frame_effects_warning(known_frame_effects) {
+                eprintln!("{}", warning);
+            }
+        }
+
+        self.write_compiled_output("EnumValues", &enum_values.attr_value_dict, pretty_print)
+    }
+
+    /// Build AllTokens, every set's tokens keyed by set code, from the set
+    /// files already written
+    pub fn build_all_tokens(&self, pretty_print: bool) -> PyResult<()> {
+        let mut all_tokens: BTreeMap<String, serde_json::Value> = BTreeMap::new();
+        self.for_each_set_file(|set_file_path, set_data| {
+            let set_code = set_data
+                .get("code")
+                .and_then(|code| code.as_str())
+                .map(str::to_string)
+                .or_else(|| {
+                    set_file_path
+                        .file_stem()
+                        .and_then(|s| s.to_str())
+                        .map(str::to_string)
+                });
+            if let Some(set_code) = set_code {
+                let tokens = set_data
+                    .get("tokens")
+                    .cloned()
+                    .unwrap_or_else(|| serde_json::json!([]));
+                all_tokens.insert(set_code, tokens);
+            }
         })?;
-        self.create_compiled_output("EnumValues", enum_values_json, pretty_print)
+
+        self.write_compiled_output("AllTokens", &all_tokens, pretty_print)
     }
 
     /// Create compiled output file - Fixed to match Python signature
@@ -451,41 +586,28 @@ impl OutputGenerator {
         data_json: String,
         pretty_print: bool,
     ) -> PyResult<()> {
-        let output_path = Path::new(&self.output_path).join(format!("{}.json", filename));
-
-        let file = fs::File::create(&output_path).map_err(|e| {
-            PyErr::new::<pyo3::exceptions::PyIOError, _>(format!("Failed to create file: {}", e))
-        })?;
-        let mut writer = BufWriter::new(file);
-
-        // Parse the JSON string back to a Value for structure creation
         let data_value: serde_json::Value = serde_json::from_str(&data_json).map_err(|e| {
             PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid JSON data: {}", e))
         })?;
+        drop(data_json);
 
-        let meta = MtgjsonMetaObject::with_current_date(None);
-        let output_structure = serde_json::json!({
-            "meta": meta,
-            "data": data_value
-        });
+        self.write_compiled_output(filename, &data_value, pretty_print)
+    }
 
-        if pretty_print {
-            serde_json::to_writer_pretty(&mut writer, &output_structure)
-        } else {
-            serde_json::to_writer(&mut writer, &output_structure)
-        }
-        .map_err(|e| {
-            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
-                "JSON serialization error: {}",
+    /// Write a single output file (such as an individual set) wrapped with meta
+    pub fn write_to_file(
+        &self,
+        file_name: &str,
+        file_contents: String,
+        pretty_print: bool,
+    ) -> PyResult<()> {
+        fs::create_dir_all(&self.output_path).map_err(|e| {
+            PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
+                "Failed to create output dir: {}",
                 e
             ))
         })?;
-
-        writer.flush().map_err(|e| {
-            PyErr::new::<pyo3::exceptions::PyIOError, _>(format!("Failed to flush file: {}", e))
-        })?;
-
-        Ok(())
+        self.create_compiled_output(file_name, file_contents, pretty_print)
     }
 
     /// Construct format map
@@ -558,14 +680,274 @@ impl OutputGenerator {
 
     /// Calculate SHA256 hash of a file
     pub fn calculate_file_hash(&self, path: String) -> PyResult<String> {
-        // TODO: Simplified hash calculation - would use a proper crypto library
         let path_obj = Path::new(&path);
         let contents = fs::read(path_obj).map_err(|e| {
             PyErr::new::<pyo3::exceptions::PyIOError, _>(format!("Failed to read file: {}", e))
         })?;
 
-        // TODO: Use sha2 crate for actual SHA256
-        Ok(format!("{:x}", contents.len()))
+        Ok(Sha256::digest(&contents)
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect())
+    }
+}
+
+/// Layout shared by every MTGJSON output file
+#[derive(Serialize)]
+struct OutputStructure<'a, T: Serialize> {
+    meta: MtgjsonMetaObject,
+    data: &'a T,
+}
+
+/// Serialize `data` to a JSON value with every object's keys sorted
+/// alphabetically, rather than in struct declaration order
+pub fn to_sorted_value<T: Serialize>(data: &T) -> PyResult<serde_json::Value> {
+    fn sort_keys(value: serde_json::Value) -> serde_json::Value {
+        match value {
+            serde_json::Value::Object(object) => {
+                let mut entries: Vec<(String, serde_json::Value)> = object.into_iter().collect();
+                entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+                serde_json::Value::Object(
+                    entries
+                        .into_iter()
+                        .map(|(key, value)| (key, sort_keys(value)))
+                        .collect(),
+                )
+            }
+            serde_json::Value::Array(values) => {
+                serde_json::Value::Array(values.into_iter().map(sort_keys).collect())
+            }
+            value => value,
+        }
+    }
+
+    serde_json::to_value(data).map(sort_keys).map_err(|e| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Serialization error: {}", e))
+    })
+}
+
+/// Serialize `data` wrapped with meta directly into `writer`
+pub fn write_output_structure<W: Write, T: Serialize>(
+    writer: W,
+    data: &T,
+    style: OutputStyle,
+) -> PyResult<()> {
+    let output_structure = OutputStructure {
+        meta: MtgjsonMetaObject::with_current_date(None),
+        data,
+    };
+
+    match style {
+        OutputStyle::Minified => serde_json::to_writer(writer, &output_structure),
+        OutputStyle::Pretty { indent } => {
+            let indent = vec![b' '; indent];
+            let formatter = serde_json::ser::PrettyFormatter::with_indent(&indent);
+            let mut serializer = serde_json::Serializer::with_formatter(writer, formatter);
+            output_structure.serialize(&mut serializer)
+        }
+    }
+    .map_err(|e| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("JSON serialization error: {}", e))
+    })
+}
+
+impl OutputGenerator {
+    /// Layout of a file written with `pretty_print`
+    pub fn output_style(&self, pretty_print: bool) -> OutputStyle {
+        if pretty_print {
+            OutputStyle::Pretty {
+                indent: self.indent,
+            }
+        } else {
+            OutputStyle::Minified
+        }
+    }
+
+    /// Serialize `data` wrapped with meta into `writer`, honoring `sort_keys`
+    fn write_output<W: Write, T: Serialize>(
+        &self,
+        writer: W,
+        data: &T,
+        style: OutputStyle,
+    ) -> PyResult<()> {
+        if self.sort_keys {
+            write_output_structure(writer, &to_sorted_value(data)?, style)
+        } else {
+            write_output_structure(writer, data, style)
+        }
+    }
+
+    /// Paths of the individual set files in the output directory
+    fn set_file_paths(&self) -> PyResult<Vec<PathBuf>> {
+        let entries = fs::read_dir(&self.output_path).map_err(|e| {
+            PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
+                "Failed to read output dir: {}",
+                e
+            ))
+        })?;
+
+        let mut set_file_paths: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.extension().and_then(|s| s.to_str()) == Some("json")
+                    && path
+                        .file_stem()
+                        .and_then(|s| s.to_str())
+                        .is_some_and(all_printings::is_set_file_name)
+            })
+            .collect();
+        set_file_paths.sort();
+        Ok(set_file_paths)
+    }
+
+    /// Call `f` with the `data` of every set file in the output directory,
+    /// reading them one at a time as untyped JSON
+    fn for_each_set_file<F>(&self, mut f: F) -> PyResult<()>
+    where
+        F: FnMut(&Path, &serde_json::Value),
+    {
+        for set_file_path in self.set_file_paths()? {
+            let contents = fs::read_to_string(&set_file_path).map_err(|e| {
+                PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
+                    "Failed to read {:?}: {}",
+                    set_file_path, e
+                ))
+            })?;
+            match serde_json::from_str::<serde_json::Value>(&contents) {
+                Ok(set_file) => f(&set_file_path, set_file.get("data").unwrap_or(&set_file)),
+                Err(e) => eprintln!("Warning: Failed to parse {:?}: {}", set_file_path, e),
+            }
+        }
+        Ok(())
+    }
+
+    /// Stream `data` wrapped with meta to `<filename>.json` without first
+    /// serializing it to a string
+    pub fn write_compiled_output<T: Serialize>(
+        &self,
+        filename: &str,
+        data: &T,
+        pretty_print: bool,
+    ) -> PyResult<()> {
+        let output_path = Path::new(&self.output_path).join(format!("{}.json", filename));
+
+        let file = fs::File::create(&output_path).map_err(|e| {
+            PyErr::new::<pyo3::exceptions::PyIOError, _>(format!("Failed to create file: {}", e))
+        })?;
+        let mut writer = BufWriter::new(file);
+
+        self.write_output(&mut writer, data, self.output_style(pretty_print))?;
+
+        writer.flush().map_err(|e| {
+            PyErr::new::<pyo3::exceptions::PyIOError, _>(format!("Failed to flush file: {}", e))
+        })?;
+
+        Ok(())
+    }
+
+    /// Stream a single output object (such as an individual set) to a file
+    pub fn write_object_to_file<T: Serialize>(
+        &self,
+        file_name: &str,
+        data: &T,
+        pretty_print: bool,
+    ) -> PyResult<()> {
+        fs::create_dir_all(&self.output_path).map_err(|e| {
+            PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
+                "Failed to create output dir: {}",
+                e
+            ))
+        })?;
+        self.write_compiled_output(file_name, data, pretty_print)
+    }
+
+    /// Stream a single output object through a `format` encoder straight to
+    /// `<file_name>.json.<ext>`, without writing the uncompressed file first.
+    /// Returns the path written.
+    pub fn write_to_file_compressed<T: Serialize>(
+        &self,
+        file_name: &str,
+        data: &T,
+        style: OutputStyle,
+        format: CompressionFormat,
+    ) -> PyResult<PathBuf> {
+        fs::create_dir_all(&self.output_path).map_err(|e| {
+            PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
+                "Failed to create output dir: {}",
+                e
+            ))
+        })?;
+        let entry_name = format!("{}.json", file_name);
+        let output_path = format.output_path(&Path::new(&self.output_path).join(&entry_name));
+        let write_error = |e: &dyn std::fmt::Display| {
+            PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
+                "Failed to write {}: {}",
+                output_path.display(),
+                e
+            ))
+        };
+
+        let file = fs::File::create(&output_path).map_err(|e| write_error(&e))?;
+        let writer = BufWriter::new(file);
+        match format {
+            CompressionFormat::Gzip => {
+                let mut encoder =
+                    flate2::write::GzEncoder::new(writer, flate2::Compression::default());
+                self.write_output(&mut encoder, data, style)?;
+                encoder.finish().and_then(|mut w| w.flush())
+            }
+            CompressionFormat::Bzip2 => {
+                let mut encoder =
+                    bzip2::write::BzEncoder::new(writer, bzip2::Compression::default());
+                self.write_output(&mut encoder, data, style)?;
+                encoder.finish().and_then(|mut w| w.flush())
+            }
+            CompressionFormat::Xz => {
+                let mut encoder = xz2::write::XzEncoder::new(writer, 6);
+                self.write_output(&mut encoder, data, style)?;
+                encoder.finish().and_then(|mut w| w.flush())
+            }
+            CompressionFormat::Zip => {
+                let options = zip::write::SimpleFileOptions::default()
+                    .compression_method(zip::CompressionMethod::Deflated);
+                let mut zip_writer = zip::ZipWriter::new(writer);
+                zip_writer
+                    .start_file(entry_name, options)
+                    .map_err(|e| write_error(&e))?;
+                self.write_output(&mut zip_writer, data, style)?;
+                let mut writer = zip_writer.finish().map_err(|e| write_error(&e))?;
+                writer.flush()
+            }
+        }
+        .map_err(|e| write_error(&e))?;
+
+        Ok(output_path)
+    }
+
+    /// Write the AllPrices archive and AllPricesToday outputs
+    pub fn write_compiled_prices_output<T: Serialize>(
+        &self,
+        all_prices: &T,
+        today_prices: &T,
+        pretty_print: bool,
+    ) -> PyResult<()> {
+        self.write_object_to_file("AllPrices", all_prices, pretty_print)?;
+        self.write_object_to_file("AllPricesToday", today_prices, pretty_print)
+    }
+
+    /// Write an output object both minified (`<file_name>.json`) and pretty
+    /// (`<file_name>.pretty.json`), serializing the object only once
+    pub fn write_object_to_files_minified_and_pretty<T: Serialize>(
+        &self,
+        file_name: &str,
+        data: &T,
+    ) -> PyResult<()> {
+        let data_value = serde_json::to_value(data).map_err(|e| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Serialization error: {}", e))
+        })?;
+
+        self.write_object_to_file(file_name, &data_value, false)?;
+        self.write_object_to_file(&format!("{}.pretty", file_name), &data_value, true)
     }
 }
 
@@ -578,7 +960,7 @@ impl Default for OutputGenerator {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
+    use std::collections::{BTreeMap, HashMap};
 
     #[test]
     fn test_output_generator_creation() {
@@ -921,4 +1303,354 @@ mod tests {
         assert_eq!(original.output_files, cloned.output_files);
         assert_eq!(original.compression_enabled, cloned.compression_enabled);
     }
+
+    /// Counts bytes written and tracks the largest single write
+    #[derive(Default)]
+    struct CountingWriter {
+        bytes_written: usize,
+        largest_write: usize,
+    }
+
+    impl Write for CountingWriter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.bytes_written += buf.len();
+            self.largest_write = self.largest_write.max(buf.len());
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_full_build_emits_observed_enum_values() {
+        let output_dir = std::env::temp_dir().join("mtgjson_enum_values_test");
+        let _ = fs::remove_dir_all(&output_dir);
+        let generator = OutputGenerator::new(Some(output_dir.display().to_string()), None);
+
+        let mut mtgjson_set = crate::classes::MtgjsonSetObject::new();
+        mtgjson_set.code = Some("TST".to_string());
+        mtgjson_set.type_ = "expansion".to_string();
+        for rarity in ["rare", "common", "rare"] {
+            let mut card = crate::classes::MtgjsonCardObject::new(false);
+            card.rarity = rarity.to_string();
+            card.availability.paper = true;
+            mtgjson_set.cards.push(card);
+        }
+        generator
+            .write_object_to_file("TST", &mtgjson_set, false)
+            .unwrap();
+
+        generator
+            .generate_compiled_output_files(Some(false))
+            .unwrap();
+
+        let read_output = |name: &str| -> serde_json::Value {
+            let contents = fs::read_to_string(output_dir.join(name)).unwrap();
+            serde_json::from_str(&contents).unwrap()
+        };
+
+        let enum_values = read_output("EnumValues.json");
+        assert_eq!(
+            enum_values["data"]["card"]["rarity"],
+            serde_json::json!(["common", "rare"])
+        );
+        assert_eq!(
+            enum_values["data"]["card"]["availability"],
+            serde_json::json!(["paper"])
+        );
+        assert_eq!(
+            enum_values["data"]["set"]["type"],
+            serde_json::json!(["expansion"])
+        );
+
+        let compiled_list = read_output("CompiledList.json");
+        assert!(compiled_list["data"]["files"]
+            .as_array()
+            .unwrap()
+            .contains(&serde_json::json!("EnumValues")));
+
+        let _ = fs::remove_dir_all(&output_dir);
+    }
+
+    #[test]
+    fn test_set_file_cards_omit_prices() {
+        let output_dir = std::env::temp_dir().join("mtgjson_omit_prices_test");
+        let _ = fs::remove_dir_all(&output_dir);
+        let generator = OutputGenerator::new(Some(output_dir.display().to_string()), None);
+
+        let mut mtgjson_set = crate::classes::MtgjsonSetObject::new();
+        mtgjson_set.code = Some("TST".to_string());
+        let mut card = crate::classes::MtgjsonCardObject::new(false);
+        card.name = "Llanowar Elves".to_string();
+        card.prices.buy_normal = Some(0.25);
+        mtgjson_set.cards.push(card);
+        generator
+            .write_object_to_file("TST", &mtgjson_set, false)
+            .unwrap();
+
+        let contents = fs::read_to_string(output_dir.join("TST.json")).unwrap();
+        let set_file: serde_json::Value = serde_json::from_str(&contents).unwrap();
+        let card = &set_file["data"]["cards"][0];
+        assert_eq!(card["name"], "Llanowar Elves");
+        assert!(card.get("prices").is_none());
+
+        let _ = fs::remove_dir_all(&output_dir);
+    }
+
+    #[test]
+    fn test_sort_keys_alphabetizes_card_keys() {
+        #[derive(serde::Deserialize)]
+        struct SetFile {
+            data: SetData,
+        }
+        #[derive(serde::Deserialize)]
+        struct SetData {
+            cards: Vec<indexmap::IndexMap<String, serde::de::IgnoredAny>>,
+        }
+
+        let output_dir = std::env::temp_dir().join("mtgjson_sort_keys_test");
+        let _ = fs::remove_dir_all(&output_dir);
+        let mut generator = OutputGenerator::new(Some(output_dir.display().to_string()), None);
+        generator.sort_keys = true;
+
+        let mut mtgjson_set = crate::classes::MtgjsonSetObject::new();
+        mtgjson_set.code = Some("TST".to_string());
+        let mut card = crate::classes::MtgjsonCardObject::new(false);
+        card.name = "Llanowar Elves".to_string();
+        card.mana_cost = "{G}".to_string();
+        card.type_ = "Creature — Elf Druid".to_string();
+        card.artist = "Anson Maddocks".to_string();
+        card.uuid = "card-uuid".to_string();
+        mtgjson_set.cards.push(card);
+        generator
+            .write_object_to_file("TST", &mtgjson_set, false)
+            .unwrap();
+
+        let contents = fs::read_to_string(output_dir.join("TST.json")).unwrap();
+        let set_file: SetFile = serde_json::from_str(&contents).unwrap();
+        let keys: Vec<&String> = set_file.data.cards[0].keys().collect();
+        let mut sorted_keys = keys.clone();
+        sorted_keys.sort();
+        assert!(keys.len() > 5);
+        assert_eq!(keys, sorted_keys);
+
+        let _ = fs::remove_dir_all(&output_dir);
+    }
+
+    #[test]
+    fn test_full_build_merges_set_files_into_all_printings() {
+        let output_dir = std::env::temp_dir().join("mtgjson_merge_all_printings_test");
+        let _ = fs::remove_dir_all(&output_dir);
+        let generator = OutputGenerator::new(Some(output_dir.display().to_string()), None);
+
+        for (code, name) in [("TST", "Test Set"), ("TS2", "Test Set Two")] {
+            let mut mtgjson_set = crate::classes::MtgjsonSetObject::new();
+            mtgjson_set.code = Some(code.to_string());
+            mtgjson_set.name = name.to_string();
+            generator
+                .write_object_to_file(code, &mtgjson_set, false)
+                .unwrap();
+        }
+        // A partially written set file is skipped rather than failing the build
+        fs::write(
+            output_dir.join("BAD.json"),
+            r#"{"meta": {}, "data": {"code": "#,
+        )
+        .unwrap();
+
+        generator
+            .generate_compiled_output_files(Some(false))
+            .unwrap();
+
+        let contents = fs::read_to_string(output_dir.join("AllPrintings.json")).unwrap();
+        let all_printings: serde_json::Value = serde_json::from_str(&contents).unwrap();
+
+        assert!(all_printings["meta"]["version"].is_string());
+        assert!(all_printings["meta"]["date"].is_string());
+        let data = all_printings["data"].as_object().unwrap();
+        assert_eq!(data.keys().collect::<Vec<_>>(), vec!["TS2", "TST"]);
+        assert_eq!(data["TST"]["name"], "Test Set");
+        assert_eq!(data["TS2"]["code"], "TS2");
+        assert_eq!(data["TS2"]["name"], "Test Set Two");
+
+        let _ = fs::remove_dir_all(&output_dir);
+    }
+
+    #[test]
+    fn test_skip_compiled_keeps_price_archive() {
+        let output_dir = std::env::temp_dir().join("mtgjson_skip_compiled_test");
+        let _ = fs::remove_dir_all(&output_dir);
+        let mut generator = OutputGenerator::new(Some(output_dir.display().to_string()), None);
+        generator.skip_compiled = true;
+
+        let mut mtgjson_set = crate::classes::MtgjsonSetObject::new();
+        mtgjson_set.code = Some("TST".to_string());
+        generator
+            .write_object_to_file("TST", &mtgjson_set, false)
+            .unwrap();
+        // A price archive from an earlier price build
+        let all_prices = serde_json::json!({
+            "uuid-1": {"paper": {"tcgplayer": {"retail": {"normal": {"2024-03-01": 1.0}}}}}
+        });
+        generator
+            .write_object_to_file("AllPrices", &all_prices, false)
+            .unwrap();
+        let archive_contents = fs::read_to_string(output_dir.join("AllPrices.json")).unwrap();
+
+        generator
+            .generate_compiled_output_files(Some(false))
+            .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(output_dir.join("AllPrices.json")).unwrap(),
+            archive_contents
+        );
+        assert!(output_dir.join("Meta.json").exists());
+        assert!(output_dir.join("AllPrintingsstandard.json").exists());
+        for compiled_file in ["AllPrintings.json", "AtomicCards.json", "SetList.json"] {
+            assert!(
+                !output_dir.join(compiled_file).exists(),
+                "{}",
+                compiled_file
+            );
+        }
+
+        generator.skip_alt_formats = true;
+        let _ = fs::remove_file(output_dir.join("AllPrintingsstandard.json"));
+        generator
+            .generate_compiled_output_files(Some(false))
+            .unwrap();
+        assert!(!output_dir.join("AllPrintingsstandard.json").exists());
+
+        let _ = fs::remove_dir_all(&output_dir);
+    }
+
+    #[test]
+    fn test_full_build_emits_all_tokens() {
+        let output_dir = std::env::temp_dir().join("mtgjson_all_tokens_test");
+        let _ = fs::remove_dir_all(&output_dir);
+        let generator = OutputGenerator::new(Some(output_dir.display().to_string()), None);
+
+        let mut mtgjson_set = crate::classes::MtgjsonSetObject::new();
+        mtgjson_set.code = Some("TST".to_string());
+        let mut token = crate::classes::MtgjsonCardObject::new(true);
+        token.name = "Soldier".to_string();
+        token.uuid = "token-uuid-1".to_string();
+        mtgjson_set.tokens.push(token);
+        generator
+            .write_object_to_file("TST", &mtgjson_set, false)
+            .unwrap();
+
+        generator
+            .generate_compiled_output_files(Some(false))
+            .unwrap();
+
+        let read_output = |name: &str| -> serde_json::Value {
+            let contents = fs::read_to_string(output_dir.join(name)).unwrap();
+            serde_json::from_str(&contents).unwrap()
+        };
+
+        let all_tokens = read_output("AllTokens.json");
+        assert!(all_tokens["meta"].is_object());
+        assert_eq!(all_tokens["data"]["TST"][0]["uuid"], "token-uuid-1");
+
+        let compiled_list = read_output("CompiledList.json");
+        assert!(compiled_list["data"]["files"]
+            .as_array()
+            .unwrap()
+            .contains(&serde_json::json!("AllTokens")));
+
+        let _ = fs::remove_dir_all(&output_dir);
+    }
+
+    #[test]
+    fn test_output_style_indentation() {
+        let data = serde_json::json!({"code": "TST"});
+        let write = |style: OutputStyle| {
+            let mut bytes = Vec::new();
+            write_output_structure(&mut bytes, &data, style).unwrap();
+            String::from_utf8(bytes).unwrap()
+        };
+
+        let minified = write(OutputStyle::Minified);
+        assert!(!minified.contains('\n'));
+        assert!(minified.contains(r#""data":{"code":"TST"}"#));
+
+        let two_spaces = write(OutputStyle::Pretty { indent: 2 });
+        assert!(two_spaces.contains("\n  \"data\": {\n    \"code\": \"TST\"\n  }"));
+
+        let four_spaces = write(OutputStyle::Pretty { indent: 4 });
+        assert!(four_spaces.contains("\n    \"data\": {\n        \"code\": \"TST\"\n    }"));
+
+        let mut generator = OutputGenerator::new(None, None);
+        assert_eq!(
+            generator.output_style(true),
+            OutputStyle::Pretty { indent: 2 }
+        );
+        generator.indent = 4;
+        assert_eq!(
+            generator.output_style(true),
+            OutputStyle::Pretty { indent: 4 }
+        );
+        assert_eq!(generator.output_style(false), OutputStyle::Minified);
+    }
+
+    #[test]
+    fn test_compressed_file_matches_uncompressed_output() {
+        use std::io::Read;
+
+        let output_dir = std::env::temp_dir().join("mtgjson_write_compressed_test");
+        let _ = fs::remove_dir_all(&output_dir);
+        let generator = OutputGenerator::new(Some(output_dir.display().to_string()), None);
+
+        let mut mtgjson_set = crate::classes::MtgjsonSetObject::new();
+        mtgjson_set.code = Some("TST".to_string());
+        let mut card = crate::classes::MtgjsonCardObject::new(false);
+        card.name = "Llanowar Elves".to_string();
+        mtgjson_set.cards.push(card);
+
+        generator
+            .write_object_to_file("TST", &mtgjson_set, true)
+            .unwrap();
+        let compressed_path = generator
+            .write_to_file_compressed(
+                "TST",
+                &mtgjson_set,
+                generator.output_style(true),
+                CompressionFormat::Gzip,
+            )
+            .unwrap();
+        assert_eq!(compressed_path, output_dir.join("TST.json.gz"));
+
+        let mut decompressed = String::new();
+        flate2::read::GzDecoder::new(fs::File::open(&compressed_path).unwrap())
+            .read_to_string(&mut decompressed)
+            .unwrap();
+        let uncompressed = fs::read_to_string(output_dir.join("TST.json")).unwrap();
+        assert_eq!(decompressed, uncompressed);
+
+        let _ = fs::remove_dir_all(&output_dir);
+    }
+
+    #[test]
+    fn test_write_output_structure_streams() {
+        let mut mtgjson_set = crate::classes::MtgjsonSetObject::new();
+        mtgjson_set.code = Some("TST".to_string());
+        for number in 0..5000 {
+            let mut card = crate::classes::MtgjsonCardObject::new(false);
+            card.name = format!("Fixture Card {}", number);
+            card.number = number.to_string();
+            card.text = "Flying, vigilance, deathtouch, lifelink".repeat(10);
+            mtgjson_set.cards.push(card);
+        }
+
+        let mut writer = CountingWriter::default();
+        write_output_structure(&mut writer, &mtgjson_set, OutputStyle::Minified).unwrap();
+
+        // Output is produced in small pieces, never as one full string
+        assert!(writer.bytes_written > 1_000_000);
+        assert!(writer.largest_write < 64 * 1024);
+    }
 }
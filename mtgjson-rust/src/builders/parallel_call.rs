@@ -3,6 +3,9 @@ use pyo3::types::{PyDict, PyList, PyTuple};
 
 use std::sync::Arc;
 use tokio::sync::Semaphore;
+
+use crate::builders::runtime::block_on_shared;
+use crate::providers::{ProviderError, ProviderResult};
 use tokio::task::JoinSet;
 
 #[pyfunction]
@@ -27,206 +30,84 @@ pub fn parallel_call(
         return Ok(empty_list.into());
     }
 
-    // Create optimized Tokio runtime with custom configuration
-    let rt = tokio::runtime::Builder::new_multi_thread()
-        .worker_threads(effective_pool_size.min(num_cpus::get()))
-        .max_blocking_threads(effective_pool_size)
-        .enable_all()
-        .build()
-        .map_err(|e| {
-            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
-                "Failed to create optimized runtime: {}",
-                e
-            ))
-        })?;
-
-    rt.block_on(async {
-        // Use Arc for efficient sharing across tasks
-        let semaphore = Arc::new(Semaphore::new(effective_pool_size));
-        let mut join_set = JoinSet::new();
-
-        // Convert repeatable arguments to PyObjects if provided
-        let repeat_objects: Option<Vec<PyObject>> =
-            repeatable_args.map(|args| args.iter().map(|arg| arg.unbind()).collect());
-
-        // Process tasks based on configuration for optimal performance
-        match (&repeat_objects, force_starmap) {
-            (Some(repeat_args), false) => {
-                // With repeatable arguments: function(arg, *repeatable_args)
-
-                for arg in args.iter() {
-                    let func_ref = function.clone_ref(py);
-                    let arg_obj = arg.unbind();
-                    let repeat_clone = repeat_args.clone();
-                    let permit = Arc::clone(&semaphore);
-
-                    join_set.spawn(async move {
-                        let _permit = permit.acquire().await.map_err(|e| {
-                            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
-                                "Semaphore error: {}",
-                                e
-                            ))
-                        })?;
-
-                        Python::with_gil(|py| -> PyResult<Py<PyAny>> {
-                            // Build argument tuple efficiently
-                            let mut call_args =
-                                Vec::<PyObject>::with_capacity(1 + repeat_clone.len());
-                            call_args.push(arg_obj);
-                            call_args.extend(repeat_clone);
-
-                            // Convert to PyTuple for function call
-                            let args_tuple = PyTuple::new_bound(py, call_args);
-
-                            // Call function(*args) matching Python behavior
-                            Ok(func_ref.call1(py, args_tuple)?.unbind())
-                        })
-                    });
-                }
-            }
-            (Some(repeat_args), true) => {
-                // With repeatable arguments and starmap: function(*arg, *repeatable_args)
-
-                for arg in args.iter() {
-                    let func_ref = function.clone();
-                    let arg_obj = arg.unbind();
-                    let repeat_clone: Vec<PyObject> =
-                        repeat_args.clone();
-                    let permit = Arc::clone(&semaphore);
-
-                    join_set.spawn(async move {
-                        let _permit = permit.acquire().await.map_err(|e| {
-                            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
-                                "Semaphore error: {}",
-                                e
-                            ))
-                        })?;
-
-                        Python::with_gil(|py| -> PyResult<Py<PyAny>> {
-                            // Handle starmap with repeatable args
-                            if let Ok(tuple_arg) = arg_obj.downcast_bound::<PyTuple>(py) {
-                                let mut all_args = Vec::new();
-                                for item in tuple_arg.iter() {
-                                    all_args.push(item.unbind());
-                                }
-                                all_args.extend(repeat_clone);
-                                let args_tuple = PyTuple::new_bound(py, all_args);
-                                Ok(func_ref.call1(py, args_tuple)?.unbind())
-                            } else if let Ok(list_arg) = arg_obj.downcast_bound::<PyList>(py) {
-                                let mut all_args = Vec::new();
-                                for item in list_arg.iter() {
-                                    all_args.push(item.unbind());
-                                }
-                                all_args.extend(repeat_clone);
-                                let args_tuple = PyTuple::new_bound(py, all_args);
-                                Ok(func_ref.call1(py, args_tuple)?.unbind())
-                            } else {
-                                // Single argument case with repeatable args
-                                let mut all_args = vec![arg_obj];
-                                all_args.extend(repeat_clone);
-                                let args_tuple = PyTuple::new_bound(py, all_args);
-                                Ok(func_ref.call1(py, args_tuple)?.unbind())
-                            }
-                        })
-                    });
-                }
-            }
-            (None, true) => {
-                // Optimized starmap case
-                for arg in args.iter() {
-                    let func_ref = function.clone();
-                    let arg_obj = arg.unbind();
-                    let permit = Arc::clone(&semaphore);
-
-                    join_set.spawn(async move {
-                        let _permit = permit.acquire().await.map_err(|e| {
-                            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
-                                "Semaphore error: {}",
-                                e
-                            ))
-                        })?;
-
-                        Python::with_gil(|py| -> PyResult<Py<PyAny>> {
-                            // Handle starmap: function(*arg)
-                            if let Ok(tuple_arg) = arg_obj.downcast_bound::<PyTuple>(py) {
-                                Ok(func_ref.call1(py, tuple_arg)?.unbind())
-                            } else if let Ok(list_arg) = arg_obj.downcast_bound::<PyList>(py) {
-                                let tuple_arg = PyTuple::new_bound(py, list_arg.iter().map(|item| item.unbind()).collect::<Vec<_>>());
-                                Ok(func_ref.call1(py, tuple_arg)?.unbind())
-                            } else {
-                                // Single argument case
-                                let single_tuple = PyTuple::new_bound(py, vec![arg_obj]);
-                                Ok(func_ref.call1(py, single_tuple)?.unbind())
-                            }
-                        })
-                    });
-                }
-            }
-            (None, false) => {
-                // Optimized normal case: function(arg)
-                for arg in args.iter() {
-                    let func_ref = function.clone();
-                    let arg_obj = arg.unbind();
-                    let permit = Arc::clone(&semaphore);
-
-                    join_set.spawn(async move {
-                        let _permit = permit.acquire().await.map_err(|e| {
-                            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
-                                "Semaphore error: {}",
-                                e
-                            ))
-                        })?;
-
-                        Python::with_gil(|py| -> PyResult<Py<PyAny>> {
-                            Ok(func_ref.call1(py, (arg_obj,))?.unbind())
-                        })
-                    });
-                }
+    // Resolve each call's positional arguments while the GIL is held, so
+    // the tasks only carry owned objects
+    let repeat_objects: Vec<PyObject> = repeatable_args
+        .map(|args| args.iter().map(|arg| arg.unbind()).collect())
+        .unwrap_or_default();
+    let calls: Vec<(PyObject, Vec<PyObject>)> = args
+        .iter()
+        .map(|arg| {
+            let mut call_args: Vec<PyObject> = if !force_starmap {
+                // function(arg, *repeatable_args)
+                vec![arg.unbind()]
+            } else if let Ok(tuple_arg) = arg.downcast::<PyTuple>() {
+                // function(*arg, *repeatable_args)
+                tuple_arg.iter().map(|item| item.unbind()).collect()
+            } else if let Ok(list_arg) = arg.downcast::<PyList>() {
+                list_arg.iter().map(|item| item.unbind()).collect()
+            } else {
+                vec![arg.unbind()]
+            };
+            call_args.extend(repeat_objects.iter().map(|obj| obj.clone_ref(py)));
+            (function.clone_ref(py), call_args)
+        })
+        .collect();
+
+    // Each task takes the GIL for its call, so it's released while waiting
+    let results = py.allow_threads(|| {
+        block_on_shared(async move {
+            // The semaphore caps the concurrency at the pool size
+            let semaphore = Arc::new(Semaphore::new(effective_pool_size));
+            let mut join_set = JoinSet::new();
+
+            for (func_ref, call_args) in calls {
+                let permit = Arc::clone(&semaphore);
+
+                join_set.spawn(async move {
+                    let _permit = permit.acquire().await.map_err(|e| {
+                        PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
+                            "Semaphore error: {}",
+                            e
+                        ))
+                    })?;
+
+                    Python::with_gil(|py| -> PyResult<Py<PyAny>> {
+                        func_ref.call1(py, PyTuple::new_bound(py, call_args))
+                    })
+                });
             }
-        }
 
-        // Collect results with pre-allocated storage
-        let mut results = Vec::with_capacity(args_len);
-        let mut error_count = 0u32;
-
-        while let Some(result) = join_set.join_next().await {
-            match result {
-                Ok(task_result) => {
-                    match task_result {
-                        Ok(value) => results.push(value),
-                        Err(e) => {
-                            error_count += 1;
-                            if error_count == 1 {
-                                // Return first error immediately for fail-fast behavior
-                                return Err(e);
-                            }
-                        }
+            // Collect results with pre-allocated storage
+            let mut results = Vec::with_capacity(args_len);
+            while let Some(result) = join_set.join_next().await {
+                match result {
+                    // Return the first error for fail-fast behavior
+                    Ok(task_result) => results.push(task_result?),
+                    Err(e) => {
+                        return Err(PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
+                            "Task execution failed: {}",
+                            e
+                        )));
                     }
                 }
-                Err(e) => {
-                    return Err(PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
-                        "Task execution failed: {}",
-                        e
-                    )));
-                }
-            }
-        }
-
-        // Process results based on fold options with optimized implementations
-        Python::with_gil(|py| -> PyResult<PyObject> {
-            if fold_list {
-                // Ultra-fast list flattening: list(itertools.chain.from_iterable(results))
-                optimize_fold_list(py, results)
-            } else if fold_dict {
-                // Ultra-fast dict merging: dict(collections.ChainMap(*results))
-                optimize_fold_dict(py, results)
-            } else {
-                // Return results as optimized list
-                let result_list = PyList::new_bound(py, results);
-                Ok(result_list.into())
             }
+            Ok(results)
         })
-    })
+    })?;
+
+    // Process results based on fold options with optimized implementations
+    if fold_list {
+        // Ultra-fast list flattening: list(itertools.chain.from_iterable(results))
+        optimize_fold_list(py, results)
+    } else if fold_dict {
+        // Ultra-fast dict merging: dict(collections.ChainMap(*results))
+        optimize_fold_dict(py, results)
+    } else {
+        // Return results as optimized list
+        let result_list = PyList::new_bound(py, results);
+        Ok(result_list.into())
+    }
 }
 
 /// Optimized list flattening with memory pre-allocation and fast iteration
@@ -371,6 +252,134 @@ impl BatchProcessor {
             self.pool_size,
         )
     }
+
+    /// Fetch many URLs concurrently. Each URL gets its own result so callers
+    /// can tell a successful body apart from a failed request.
+    pub fn parallel_api_calls(&self, urls: Vec<String>) -> PyResult<Vec<ApiCallResult>> {
+        let results = block_on_shared(fetch_urls(&urls, self.pool_size));
+
+        Ok(urls
+            .into_iter()
+            .zip(results)
+            .map(|(url, result)| ApiCallResult::from_result(url, result))
+            .collect())
+    }
+}
+
+/// Outcome of a single request made by `parallel_api_calls`
+#[pyclass(name = "ApiCallResult")]
+#[derive(Debug, Clone)]
+pub struct ApiCallResult {
+    #[pyo3(get)]
+    pub url: String,
+    #[pyo3(get)]
+    pub status: Option<u16>,
+    #[pyo3(get)]
+    pub body: Option<String>,
+    #[pyo3(get)]
+    pub error: Option<String>,
+}
+
+#[pymethods]
+impl ApiCallResult {
+    /// True when the request completed with a successful HTTP status
+    pub fn is_ok(&self) -> bool {
+        self.error.is_none()
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!(
+            "ApiCallResult(url={}, status={:?}, error={:?})",
+            self.url, self.status, self.error
+        )
+    }
+}
+
+impl ApiCallResult {
+    fn from_result(url: String, result: ProviderResult<(u16, String)>) -> Self {
+        match result {
+            Ok((status, body)) => Self {
+                url,
+                status: Some(status),
+                body: Some(body),
+                error: None,
+            },
+            Err(e) => Self {
+                url,
+                status: None,
+                body: None,
+                error: Some(e.to_string()),
+            },
+        }
+    }
+}
+
+/// Fetch a single URL, treating transport failures and non-success statuses as errors
+async fn fetch_url(client: &reqwest::Client, url: &str) -> ProviderResult<(u16, String)> {
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| ProviderError::NetworkError(format!("Request to {} failed: {}", url, e)))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        return Err(ProviderError::NetworkError(format!(
+            "Request to {} returned status {}",
+            url, status
+        )));
+    }
+
+    let body = response
+        .text()
+        .await
+        .map_err(|e| ProviderError::ParseError(format!("Failed to read {}: {}", url, e)))?;
+    Ok((status.as_u16(), body))
+}
+
+/// Fetch every URL concurrently, returning one result per URL in input order
+pub async fn fetch_urls(
+    urls: &[String],
+    max_concurrent: usize,
+) -> Vec<ProviderResult<(u16, String)>> {
+    let client = reqwest::Client::new();
+    let semaphore = Arc::new(Semaphore::new(max_concurrent.max(1)));
+    let mut join_set = JoinSet::new();
+
+    for (index, url) in urls.iter().cloned().enumerate() {
+        let client = client.clone();
+        let permit = Arc::clone(&semaphore);
+        join_set.spawn(async move {
+            let result = match permit.acquire().await {
+                Ok(_permit) => fetch_url(&client, &url).await,
+                Err(e) => Err(ProviderError::ProcessingError(format!(
+                    "Semaphore error: {}",
+                    e
+                ))),
+            };
+            (index, result)
+        });
+    }
+
+    let mut results: Vec<Option<ProviderResult<(u16, String)>>> =
+        (0..urls.len()).map(|_| None).collect();
+    while let Some(joined) = join_set.join_next().await {
+        match joined {
+            Ok((index, result)) => results[index] = Some(result),
+            Err(e) => eprintln!("Warning: API call task failed to join: {}", e),
+        }
+    }
+
+    results
+        .into_iter()
+        .map(|result| {
+            result.unwrap_or_else(|| {
+                Err(ProviderError::ProcessingError(
+                    "API call task did not complete".to_string(),
+                ))
+            })
+        })
+        .collect()
 }
 
 /// Ultra-fast async task queue for high-throughput processing
@@ -554,6 +563,7 @@ pub fn parallel_call_module(m: &Bound<PyModule>) -> PyResult<()> {
     m.add_class::<BatchProcessor>()?;
     m.add_class::<AsyncTaskQueue>()?;
     m.add_class::<CardBuildProcessor>()?;
+    m.add_class::<ApiCallResult>()?;
 
     Ok(())
 }
@@ -605,6 +615,57 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_parallel_call_repeatable_args_with_and_without_starmap() {
+        Python::with_gil(|py| {
+            let code = "def pair_func(pair, offset): return pair[0] + pair[1] + offset\n\
+                        def star_func(a, b, offset): return a + b + offset";
+            let module =
+                pyo3::types::PyModule::from_code_bound(py, code, "pair_func", "pair_func").unwrap();
+            let pair_func = module.getattr("pair_func").unwrap().into();
+            let star_func = module.getattr("star_func").unwrap().into();
+
+            let data = PyList::new_bound(
+                py,
+                vec![PyTuple::new_bound(py, vec![1, 2]), PyTuple::new_bound(py, vec![3, 4])],
+            );
+            let repeat = PyList::new_bound(py, vec![10]);
+
+            // Without starmap each tuple is handed over whole
+            let result =
+                parallel_call(py, pair_func, &data, Some(&repeat), false, false, false, 4).unwrap();
+            let mut values: Vec<i64> = result.extract(py).unwrap();
+            values.sort();
+            assert_eq!(values, vec![13, 17]);
+
+            // With starmap each tuple is unpacked into positional arguments
+            let result =
+                parallel_call(py, star_func, &data, Some(&repeat), false, false, true, 4).unwrap();
+            let mut values: Vec<i64> = result.extract(py).unwrap();
+            values.sort();
+            assert_eq!(values, vec![13, 17]);
+        });
+    }
+
+    #[test]
+    fn test_parallel_api_calls_reports_failures() {
+        let processor = BatchProcessor::new(Some(2), None);
+        let urls = vec![
+            "http://127.0.0.1:9/unreachable".to_string(),
+            "not a url".to_string(),
+        ];
+
+        let results = processor.parallel_api_calls(urls.clone()).unwrap();
+        assert_eq!(results.len(), 2);
+        for (result, url) in results.iter().zip(urls) {
+            assert_eq!(result.url, url);
+            assert!(!result.is_ok());
+            assert!(result.body.is_none());
+            assert!(result.status.is_none());
+            assert!(result.error.is_some());
+        }
+    }
+
     #[test]
     fn test_batch_processor() {
         Python::with_gil(|py| {
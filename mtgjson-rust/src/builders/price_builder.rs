@@ -1,10 +1,24 @@
 // MTGJSON price builder - price data processing and compression
-use chrono::Utc;
+use chrono::{NaiveDate, Utc};
 use pyo3::prelude::*;
 
 use pyo3::types::{PyDict, PyTuple};
+use serde::de::{self, DeserializeSeed, Deserializer, IgnoredAny, MapAccess, Visitor};
+use serde_json::Value;
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::fmt;
+use std::fs;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+use crate::classes::meta::MtgjsonMetaObject;
+use crate::classes::MtgjsonPricesObject;
+use crate::providers::{
+    CardHoarderProvider, CardKingdomProvider, CardMarketProvider, TCGPlayerProvider,
+};
+
+/// How many days of price history the archive keeps by default
+pub const DEFAULT_PRICE_RETENTION_DAYS: i64 = 90;
 
 /// MTGJSON Price Builder - Exact Python API compatibility
 #[derive(Debug)]
@@ -14,6 +28,15 @@ pub struct PriceBuilder {
     pub providers: Vec<PyObject>, // List of AbstractProvider instances
     #[pyo3(get, set)]
     pub all_printings_path: Option<PathBuf>,
+    /// Days of price history kept when the archive is pruned
+    #[pyo3(get, set)]
+    pub retention_days: i64,
+    /// Only keep price datapoints dated on or after this `YYYY-MM-DD` date
+    #[pyo3(get, set)]
+    pub since: Option<String>,
+    /// Existing AllPrices archive that today's prices are merged into
+    #[pyo3(get, set)]
+    pub price_archive_path: Option<PathBuf>,
 }
 
 #[pymethods]
@@ -34,6 +57,9 @@ impl PriceBuilder {
         Self {
             providers: provider_list,
             all_printings_path,
+            retention_days: DEFAULT_PRICE_RETENTION_DAYS,
+            since: None,
+            price_archive_path: None,
         }
     }
 
@@ -90,19 +116,37 @@ impl PriceBuilder {
 
     /// The full build prices operation - Prune & Update remote database
     /// Returns: Tuple[Dict[str, Any], Dict[str, Any]] - (archive_prices, today_prices)
-    pub fn build_prices(&self) -> PyResult<(HashMap<String, Py<PyAny>>, HashMap<String, Py<PyAny>>)> {
-        let today_prices = self.build_today_prices()?;
+    pub fn build_prices(&self, py: Python<'_>) -> PyResult<(PyObject, PyObject)> {
+        let all_printings_path = self.all_printings_path.as_ref().ok_or_else(|| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "Unable to build prices. No AllPrintings path given",
+            )
+        })?;
+        if !all_printings_path.exists() {
+            return Err(PyErr::new::<pyo3::exceptions::PyFileNotFoundError, _>(
+                format!(
+                    "Unable to build prices. AllPrintings not found in {:?}",
+                    all_printings_path
+                ),
+            ));
+        }
 
-        // In real implementation, would download and merge with archive
-        // Create a new HashMap since PyObject doesn't implement Clone
-        let mut archive_prices = HashMap::new();
-        Python::with_gil(|py| {
-            for (key, value) in &today_prices {
-                archive_prices.insert(key.clone(), value.clone());
-            }
-        });
+        let (archive_prices, today_prices) = py.allow_threads(|| {
+            build_prices_from_providers(
+                &all_printings_path.to_string_lossy(),
+                self.price_archive_path.as_deref(),
+                self.retention_days,
+                self.since.as_deref(),
+            )
+        })?;
 
-        Ok((archive_prices, today_prices))
+        let json_module = pyo3::types::PyModule::import_bound(py, "json")?;
+        let to_python = |prices: &Value| -> PyResult<PyObject> {
+            Ok(json_module
+                .call_method1("loads", (prices.to_string(),))?
+                .unbind())
+        };
+        Ok((to_python(&archive_prices)?, to_python(&today_prices)?))
     }
 
     /// Prune entries from the MTGJSON database that are older than `months` old
@@ -159,6 +203,444 @@ impl PriceBuilder {
     }
 }
 
+/// Price series are keyed by `YYYY-MM-DD` dates
+fn is_price_date(key: &str) -> bool {
+    chrono::NaiveDate::parse_from_str(key, "%Y-%m-%d").is_ok()
+}
+
+/// Load an existing AllPrices archive, unwrapping its `data` block if present
+pub fn load_price_archive(path: &Path) -> PyResult<Value> {
+    let contents = fs::read_to_string(path).map_err(|e| {
+        PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
+            "Failed to read price archive {:?}: {}",
+            path, e
+        ))
+    })?;
+    let mut archive: Value = serde_json::from_str(&contents).map_err(|e| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "Invalid price archive {:?}: {}",
+            path, e
+        ))
+    })?;
+
+    Ok(match archive.get_mut("data") {
+        Some(data) => data.take(),
+        None => archive,
+    })
+}
+
+/// Prices from the latest date anywhere in `prices`, keeping only the
+/// series that have an entry on that date
+pub fn latest_price_data(prices: &Value) -> Value {
+    match latest_price_date(prices) {
+        Some(date) => price_data_on(prices, &date),
+        None => Value::Object(serde_json::Map::new()),
+    }
+}
+
+/// The most recent price date found at any depth of `prices`
+fn latest_price_date(prices: &Value) -> Option<String> {
+    let Value::Object(map) = prices else {
+        return None;
+    };
+    map.iter()
+        .filter_map(|(key, value)| {
+            if is_price_date(key) {
+                Some(key.clone())
+            } else {
+                latest_price_date(value)
+            }
+        })
+        .max()
+}
+
+/// The entries of `prices` dated `date`, along with the non-date fields of
+/// every map that still holds one
+fn price_data_on(prices: &Value, date: &str) -> Value {
+    let Value::Object(map) = prices else {
+        return prices.clone();
+    };
+    Value::Object(
+        map.iter()
+            .filter(|(key, _)| !is_price_date(key) || key.as_str() == date)
+            .map(|(key, value)| {
+                if is_price_date(key) {
+                    (key.clone(), value.clone())
+                } else {
+                    (key.clone(), price_data_on(value, date))
+                }
+            })
+            .filter(|(_, value)| !is_empty_price_map(value))
+            .collect(),
+    )
+}
+
+/// Deep merge `today` into `archive`, with today's prices winning
+pub fn merge_price_data(archive: &mut Value, today: &Value) {
+    match (archive, today) {
+        (Value::Object(archive_map), Value::Object(today_map)) => {
+            for (key, value) in today_map {
+                match archive_map.get_mut(key) {
+                    Some(existing) => merge_price_data(existing, value),
+                    None => {
+                        archive_map.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (archive, today) => *archive = today.clone(),
+    }
+}
+
+/// Remove every dated price older than `cutoff` (`YYYY-MM-DD`), along with
+/// any entries left empty by doing so
+pub fn prune_price_data(content: &mut Value, cutoff: &str) {
+    if let Value::Object(map) = content {
+        map.retain(|key, value| {
+            if is_price_date(key) && key.as_str() < cutoff {
+                return false;
+            }
+            prune_price_data(value, cutoff);
+            !is_empty_price_map(value)
+        });
+    }
+}
+
+/// A map left with no prices, only its `currency`, once old dates are pruned
+fn is_empty_price_map(value: &Value) -> bool {
+    matches!(value, Value::Object(map) if map.keys().all(|key| key == "currency"))
+}
+
+/// Oldest date (`YYYY-MM-DD`) kept when retaining `retention_days` of
+/// history up to `today`
+fn price_archive_cutoff(today: NaiveDate, retention_days: i64) -> String {
+    let prune_date = today - chrono::Duration::days(retention_days);
+    prune_date.format("%Y-%m-%d").to_string()
+}
+
+impl PriceBuilder {
+    /// Drop every dated price point in `archive` older than `retention_days`
+    /// before `today`, along with any provider or price type maps left empty
+    pub fn prune_archive(archive: &mut Value, today: NaiveDate, retention_days: i64) {
+        prune_price_data(archive, &price_archive_cutoff(today, retention_days));
+    }
+}
+
+/// Drop every datapoint dated before `since` (`YYYY-MM-DD`) from both the
+/// archive and today's prices. Unlike the retention window this is a fixed
+/// floor, for price servers that only want recent data.
+pub fn apply_since_floor(archive_prices: &mut Value, today_prices: &mut Value, since: &str) {
+    prune_price_data(archive_prices, since);
+    prune_price_data(today_prices, since);
+}
+
+/// Build `(archive, today)` prices from an existing AllPrices archive rather
+/// than live providers, running only the merge and prune steps against it
+pub fn build_prices_from_archive(
+    path: &Path,
+    retention_days: i64,
+    since: Option<&str>,
+) -> PyResult<(Value, Value)> {
+    let mut archive_prices = load_price_archive(path)?;
+    let mut today_prices = latest_price_data(&archive_prices);
+
+    merge_price_data(&mut archive_prices, &today_prices);
+    PriceBuilder::prune_archive(&mut archive_prices, Utc::now().date_naive(), retention_days);
+    if let Some(since) = since {
+        apply_since_floor(&mut archive_prices, &mut today_prices, since);
+    }
+
+    Ok((archive_prices, today_prices))
+}
+
+/// Add one provider's prices to `today`, keyed by UUID. Entries are deep
+/// merged so prices from other providers or finishes are kept.
+pub fn merge_provider_prices(
+    today: &mut Value,
+    provider_prices: &HashMap<String, MtgjsonPricesObject>,
+) {
+    let Value::Object(today_map) = today else {
+        return;
+    };
+    for (uuid, prices) in provider_prices {
+        let entry = prices.to_price_entry();
+        match today_map.get_mut(uuid) {
+            Some(existing) => merge_price_data(existing, &entry),
+            None => {
+                today_map.insert(uuid.clone(), entry);
+            }
+        }
+    }
+}
+
+type ProviderPrices = fn(&str) -> PyResult<HashMap<String, MtgjsonPricesObject>>;
+
+/// Every native price provider, by name
+const PRICE_PROVIDERS: [(&str, ProviderPrices); 4] = [
+    ("CardHoarder", |path| {
+        CardHoarderProvider::new()?.generate_today_price_dict(path)
+    }),
+    ("CardKingdom", |path| {
+        CardKingdomProvider::new()?.generate_today_price_dict(path)
+    }),
+    ("CardMarket", |path| {
+        CardMarketProvider::new(None, None)?.generate_today_price_dict(path.to_string())
+    }),
+    ("TCGPlayer", |path| {
+        TCGPlayerProvider::new()?.generate_today_price_dict(path)
+    }),
+];
+
+/// Run every price provider concurrently and merge their prices for today.
+/// A provider that fails is logged and skipped.
+pub fn build_today_prices_from_providers(all_printings_path: &str) -> Value {
+    let results: Vec<_> = std::thread::scope(|scope| {
+        let handles: Vec<_> = PRICE_PROVIDERS
+            .iter()
+            .map(|(name, generate)| (*name, scope.spawn(move || generate(all_printings_path))))
+            .collect();
+        handles
+            .into_iter()
+            .map(|(name, handle)| (name, handle.join()))
+            .collect()
+    });
+
+    let mut today_prices = Value::Object(serde_json::Map::new());
+    for (name, result) in results {
+        match result {
+            Ok(Ok(provider_prices)) => merge_provider_prices(&mut today_prices, &provider_prices),
+            Ok(Err(e)) => eprintln!("Warning: {} failed to generate prices: {}", name, e),
+            Err(_) => eprintln!("Warning: {} panicked while generating prices", name),
+        }
+    }
+    if matches!(&today_prices, Value::Object(map) if map.is_empty()) {
+        eprintln!("Warning: No price data generated from any provider");
+    }
+
+    today_prices
+}
+
+/// Build `(archive, today)` prices from the live providers. Today's prices
+/// are merged into the archive at `archive_path`, if given, which is then
+/// pruned to the last `retention_days` and, if given, to dates from `since`.
+pub fn build_prices_from_providers(
+    all_printings_path: &str,
+    archive_path: Option<&Path>,
+    retention_days: i64,
+    since: Option<&str>,
+) -> PyResult<(Value, Value)> {
+    let mut today_prices = build_today_prices_from_providers(all_printings_path);
+
+    let mut archive_prices = match archive_path {
+        Some(archive_path) => {
+            update_price_archive_file(archive_path, &today_prices, retention_days)?;
+            load_price_archive(archive_path)?
+        }
+        None => {
+            let mut archive_prices = Value::Object(serde_json::Map::new());
+            merge_price_data(&mut archive_prices, &today_prices);
+            archive_prices
+        }
+    };
+    PriceBuilder::prune_archive(&mut archive_prices, Utc::now().date_naive(), retention_days);
+    if let Some(since) = since {
+        apply_since_floor(&mut archive_prices, &mut today_prices, since);
+    }
+
+    Ok((archive_prices, today_prices))
+}
+
+/// Embed each card's entry from `card_prices` (prices keyed by UUID) as its
+/// `prices` field in a serialized set, the layout legacy consumers expect
+pub fn inject_card_prices(set_data: &mut Value, card_prices: &Value) {
+    let Some(Value::Array(cards)) = set_data.get_mut("cards") else {
+        return;
+    };
+
+    for card in cards.iter_mut() {
+        let prices = card
+            .get("uuid")
+            .and_then(|uuid| uuid.as_str())
+            .and_then(|uuid| card_prices.get(uuid))
+            .cloned();
+        if let (Some(prices), Value::Object(card)) = (prices, card) {
+            card.insert("prices".to_string(), prices);
+        }
+    }
+}
+
+/// Merges today's prices into archive entries one UUID at a time and writes
+/// each result out as a member of the `data` object
+struct PriceEntryWriter<W: Write> {
+    writer: W,
+    pending_today: serde_json::Map<String, Value>,
+    cutoff: String,
+    entries_written: usize,
+}
+
+impl<W: Write> PriceEntryWriter<W> {
+    fn write_entry(&mut self, uuid: &str, mut entry: Value) -> io::Result<()> {
+        if let Some(today_entry) = self.pending_today.remove(uuid) {
+            merge_price_data(&mut entry, &today_entry);
+        }
+        prune_price_data(&mut entry, &self.cutoff);
+        if matches!(&entry, Value::Object(map) if map.is_empty()) {
+            return Ok(());
+        }
+
+        if self.entries_written > 0 {
+            self.writer.write_all(b",")?;
+        }
+        serde_json::to_writer(&mut self.writer, uuid)?;
+        self.writer.write_all(b":")?;
+        serde_json::to_writer(&mut self.writer, &entry)?;
+        self.entries_written += 1;
+        Ok(())
+    }
+}
+
+/// Walks an AllPrices archive, with or without its `{meta, data}` wrapper,
+/// handing each UUID entry to a `PriceEntryWriter` as soon as it is parsed
+struct PriceArchiveVisitor<'a, W: Write> {
+    entries: &'a mut PriceEntryWriter<W>,
+    top_level: bool,
+}
+
+impl<'de, W: Write> DeserializeSeed<'de> for PriceArchiveVisitor<'_, W> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, W: Write> Visitor<'de> for PriceArchiveVisitor<'_, W> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an AllPrices archive object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "meta" if self.top_level => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+                "data" if self.top_level => map.next_value_seed(PriceArchiveVisitor {
+                    entries: &mut *self.entries,
+                    top_level: false,
+                })?,
+                _ => {
+                    let entry = map.next_value::<Value>()?;
+                    self.entries
+                        .write_entry(&key, entry)
+                        .map_err(de::Error::custom)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn stream_merge_entries<R: Read, W: Write>(
+    archive: R,
+    today: &Value,
+    cutoff: &str,
+    writer: W,
+) -> io::Result<()> {
+    let mut entries = PriceEntryWriter {
+        writer,
+        pending_today: today.as_object().cloned().unwrap_or_default(),
+        cutoff: cutoff.to_string(),
+        entries_written: 0,
+    };
+
+    entries.writer.write_all(b"{\"meta\":")?;
+    serde_json::to_writer(
+        &mut entries.writer,
+        &MtgjsonMetaObject::with_current_date(None),
+    )?;
+    entries.writer.write_all(b",\"data\":{")?;
+
+    let mut deserializer = serde_json::Deserializer::from_reader(archive);
+    PriceArchiveVisitor {
+        entries: &mut entries,
+        top_level: true,
+    }
+    .deserialize(&mut deserializer)?;
+    deserializer.end()?;
+
+    // Cards that have no history in the archive yet
+    for (uuid, entry) in std::mem::take(&mut entries.pending_today) {
+        entries.write_entry(&uuid, entry)?;
+    }
+
+    entries.writer.write_all(b"}}")?;
+    entries.writer.flush()
+}
+
+/// Merge `today` prices into the AllPrices archive read from `archive` and
+/// prune anything older than `cutoff`, writing the result to `writer` one
+/// UUID at a time instead of holding the full price history in memory
+pub fn stream_merge_price_archive<R: Read, W: Write>(
+    archive: R,
+    today: &Value,
+    cutoff: &str,
+    writer: W,
+) -> PyResult<()> {
+    stream_merge_entries(archive, today, cutoff, writer).map_err(|e| {
+        PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
+            "Failed to stream price archive: {}",
+            e
+        ))
+    })
+}
+
+/// Stream-merge `today` prices into the archive at `archive_path`, writing
+/// the archive pruned to the last `retention_days` to `output_path`
+pub fn merge_price_archive_file(
+    archive_path: &Path,
+    today: &Value,
+    output_path: &Path,
+    retention_days: i64,
+) -> PyResult<()> {
+    let open_error = |path: &Path, e: io::Error| {
+        PyErr::new::<pyo3::exceptions::PyIOError, _>(format!("Failed to open {:?}: {}", path, e))
+    };
+    let archive: Box<dyn Read> = match fs::File::open(archive_path) {
+        Ok(archive) => Box::new(BufReader::new(archive)),
+        // With no archive yet, the merged archive is just today's prices
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Box::new("{}".as_bytes()),
+        Err(e) => return Err(open_error(archive_path, e)),
+    };
+    let output = fs::File::create(output_path).map_err(|e| open_error(output_path, e))?;
+
+    stream_merge_price_archive(
+        archive,
+        today,
+        &price_archive_cutoff(Utc::now().date_naive(), retention_days),
+        BufWriter::new(output),
+    )
+}
+
+/// Stream-merge `today` prices into the archive at `archive_path` in place,
+/// creating it if it doesn't exist yet
+pub fn update_price_archive_file(
+    archive_path: &Path,
+    today: &Value,
+    retention_days: i64,
+) -> PyResult<()> {
+    let merged_path = archive_path.with_extension("merged.json");
+    merge_price_archive_file(archive_path, today, &merged_path, retention_days)?;
+    fs::rename(&merged_path, archive_path).map_err(|e| {
+        PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
+            "Failed to replace price archive {:?}: {}",
+            archive_path, e
+        ))
+    })
+}
+
 impl Default for PriceBuilder {
     fn default() -> Self {
         Python::with_gil(|py| {
@@ -167,3 +649,260 @@ impl Default for PriceBuilder {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prune_archive_straddling_cutoff() {
+        let mut archive = serde_json::json!({
+            "uuid-1": {"paper": {
+                "cardkingdom": {
+                    "retail": {"normal": {
+                        "2024-01-30": 1.0,
+                        "2024-01-31": 2.0,
+                        "2024-04-30": 3.0
+                    }},
+                    "buylist": {"foil": {"2024-01-01": 0.5}},
+                    "currency": "USD"
+                },
+                "cardhoarder": {
+                    "retail": {"normal": {"2024-01-15": 0.25}},
+                    "currency": "USD"
+                }
+            }},
+            "uuid-2": {"mtgo": {"cardhoarder": {
+                "retail": {"foil": {"2023-12-31": 4.0}},
+                "currency": "USD"
+            }}}
+        });
+        let today = NaiveDate::from_ymd_opt(2024, 4, 30).unwrap();
+
+        PriceBuilder::prune_archive(&mut archive, today, DEFAULT_PRICE_RETENTION_DAYS);
+
+        assert_eq!(
+            archive,
+            serde_json::json!({"uuid-1": {"paper": {"cardkingdom": {
+                "retail": {"normal": {"2024-01-31": 2.0, "2024-04-30": 3.0}},
+                "currency": "USD"
+            }}}})
+        );
+
+        PriceBuilder::prune_archive(&mut archive, today, 30);
+        assert_eq!(
+            archive["uuid-1"]["paper"]["cardkingdom"]["retail"]["normal"],
+            serde_json::json!({"2024-04-30": 3.0})
+        );
+    }
+
+    #[test]
+    fn test_since_floor_excludes_earlier_datapoints() {
+        let mut archive = serde_json::json!({
+            "uuid-1": {"paper": {"tcgplayer": {
+                "retail": {"normal": {"2024-02-28": 1.0, "2024-03-01": 2.0, "2024-03-05": 3.0}},
+                "currency": "USD"
+            }}},
+            "uuid-2": {"paper": {"cardkingdom": {
+                "retail": {"foil": {"2024-01-15": 4.0}},
+                "currency": "USD"
+            }}}
+        });
+        let mut today = serde_json::json!({
+            "uuid-1": {"paper": {"tcgplayer": {"retail": {"normal": {"2024-03-05": 3.0}}}}},
+            "uuid-2": {"paper": {"cardkingdom": {"retail": {"foil": {"2024-01-15": 4.0}}}}}
+        });
+
+        apply_since_floor(&mut archive, &mut today, "2024-03-01");
+
+        assert_eq!(
+            archive["uuid-1"]["paper"]["tcgplayer"]["retail"]["normal"],
+            serde_json::json!({"2024-03-01": 2.0, "2024-03-05": 3.0})
+        );
+        assert!(archive.get("uuid-2").is_none());
+        assert!(today.get("uuid-1").is_some());
+        assert!(today.get("uuid-2").is_none());
+    }
+
+    #[test]
+    fn test_latest_price_data_keeps_series_priced_on_archive_latest_date() {
+        let archive = serde_json::json!({
+            "uuid-1": {"paper": {"tcgplayer": {
+                "retail": {
+                    "normal": {"2024-03-04": 1.0, "2024-03-05": 2.0},
+                    "foil": {"2024-03-04": 5.0}
+                },
+                "currency": "USD"
+            }}},
+            "uuid-2": {"paper": {"cardkingdom": {
+                "retail": {"normal": {"2024-03-04": 4.0}},
+                "currency": "USD"
+            }}}
+        });
+
+        let today = latest_price_data(&archive);
+
+        assert_eq!(
+            today,
+            serde_json::json!({
+                "uuid-1": {"paper": {"tcgplayer": {
+                    "retail": {"normal": {"2024-03-05": 2.0}},
+                    "currency": "USD"
+                }}}
+            })
+        );
+    }
+
+    #[test]
+    fn test_merge_provider_prices_keeps_every_provider() {
+        let date = "2024-01-15".to_string();
+        let mut card_kingdom = MtgjsonPricesObject::default();
+        card_kingdom.source = "paper".to_string();
+        card_kingdom.provider = "cardkingdom".to_string();
+        card_kingdom.date = date.clone();
+        card_kingdom.buy_normal = Some(1.25);
+        card_kingdom.sell_normal = Some(2.5);
+        let mut tcgplayer = MtgjsonPricesObject::default();
+        tcgplayer.source = "paper".to_string();
+        tcgplayer.provider = "tcgplayer".to_string();
+        tcgplayer.date = date.clone();
+        tcgplayer.sell_foil = Some(9.0);
+
+        let mut today = serde_json::json!({});
+        merge_provider_prices(
+            &mut today,
+            &HashMap::from([("uuid-1".to_string(), card_kingdom)]),
+        );
+        merge_provider_prices(
+            &mut today,
+            &HashMap::from([("uuid-1".to_string(), tcgplayer)]),
+        );
+
+        assert_eq!(
+            today,
+            serde_json::json!({"uuid-1": {"paper": {
+                "cardkingdom": {
+                    "buylist": {"normal": {"2024-01-15": 1.25}},
+                    "retail": {"normal": {"2024-01-15": 2.5}},
+                    "currency": "USD"
+                },
+                "tcgplayer": {
+                    "retail": {"foil": {"2024-01-15": 9.0}},
+                    "currency": "USD"
+                }
+            }}})
+        );
+    }
+
+    #[test]
+    fn test_inject_card_prices_only_when_requested() {
+        use crate::classes::{MtgjsonCardObject, MtgjsonSetObject};
+
+        let mut mtgjson_set = MtgjsonSetObject::new();
+        mtgjson_set.code = Some("TST".to_string());
+        for uuid in ["uuid-1", "uuid-2"] {
+            let mut card = MtgjsonCardObject::new(false);
+            card.uuid = uuid.to_string();
+            mtgjson_set.cards.push(card);
+        }
+        let card_prices = serde_json::json!({
+            "uuid-1": {"paper": {"tcgplayer": {"retail": {"normal": {"2024-03-01": 1.0}}}}}
+        });
+
+        let set_data = serde_json::to_value(&mtgjson_set).unwrap();
+        assert!(set_data["cards"][0].get("prices").is_none());
+
+        let mut priced_set_data = set_data.clone();
+        inject_card_prices(&mut priced_set_data, &card_prices);
+        assert_eq!(priced_set_data["cards"][0]["prices"], card_prices["uuid-1"]);
+        assert!(priced_set_data["cards"][1].get("prices").is_none());
+    }
+
+    #[test]
+    fn test_stream_merge_matches_in_memory_merge() {
+        let archive = serde_json::json!({
+            "meta": {"date": "2024-03-01", "version": "5.2.2"},
+            "data": {
+                "uuid-1": {
+                    "paper": {
+                        "tcgplayer": {
+                            "currency": "USD",
+                            "retail": {"normal": {"2023-10-01": 0.5, "2024-03-01": 1.0}}
+                        }
+                    }
+                },
+                "uuid-2": {
+                    "paper": {
+                        "cardkingdom": {"currency": "USD", "retail": {"foil": {"2023-10-01": 4.0}}}
+                    }
+                }
+            }
+        });
+        let today = serde_json::json!({
+            "uuid-1": {"paper": {"tcgplayer": {"retail": {"normal": {"2024-03-02": 1.25}}}}},
+            "uuid-3": {
+                "mtgo": {
+                    "cardhoarder": {"currency": "USD", "retail": {"normal": {"2024-03-02": 0.02}}}
+                }
+            }
+        });
+        let cutoff = "2024-01-01";
+
+        let mut in_memory = archive["data"].clone();
+        merge_price_data(&mut in_memory, &today);
+        prune_price_data(&mut in_memory, cutoff);
+
+        let mut streamed = Vec::new();
+        stream_merge_price_archive(
+            archive.to_string().as_bytes(),
+            &today,
+            cutoff,
+            &mut streamed,
+        )
+        .unwrap();
+        let streamed: Value = serde_json::from_slice(&streamed).unwrap();
+
+        assert!(streamed["meta"].is_object());
+        assert_eq!(streamed["data"], in_memory);
+        assert!(streamed["data"].get("uuid-2").is_none());
+        assert_eq!(
+            streamed["data"]["uuid-1"]["paper"]["tcgplayer"]["retail"]["normal"],
+            serde_json::json!({"2024-03-01": 1.0, "2024-03-02": 1.25})
+        );
+    }
+
+    #[test]
+    fn test_update_price_archive_file_merges_in_place() {
+        let archive_dir = std::env::temp_dir().join("mtgjson_update_price_archive_test");
+        let _ = fs::remove_dir_all(&archive_dir);
+        fs::create_dir_all(&archive_dir).unwrap();
+        let archive_path = archive_dir.join("AllPrices.json");
+
+        let date = |days_ago: i64| {
+            (Utc::now() - chrono::Duration::days(days_ago))
+                .format("%Y-%m-%d")
+                .to_string()
+        };
+        let (today, yesterday) = (date(0), date(1));
+        let first_day = serde_json::json!({
+            "uuid-1": {"paper": {"tcgplayer": {"retail": {"normal": {&yesterday: 1.0}}}}}
+        });
+        let second_day = serde_json::json!({
+            "uuid-1": {"paper": {"tcgplayer": {"retail": {"normal": {&today: 1.25}}}}}
+        });
+
+        // The first update starts the archive, the second adds to it
+        update_price_archive_file(&archive_path, &first_day, DEFAULT_PRICE_RETENTION_DAYS).unwrap();
+        update_price_archive_file(&archive_path, &second_day, DEFAULT_PRICE_RETENTION_DAYS)
+            .unwrap();
+
+        let archive = load_price_archive(&archive_path).unwrap();
+        assert_eq!(
+            archive["uuid-1"]["paper"]["tcgplayer"]["retail"]["normal"],
+            serde_json::json!({&yesterday: 1.0, &today: 1.25})
+        );
+        assert!(!archive_path.with_extension("merged.json").exists());
+
+        let _ = fs::remove_dir_all(&archive_dir);
+    }
+}
@@ -0,0 +1,157 @@
+// Shared loader for the JSON files in mtgjson5/resources
+use serde::de::DeserializeOwned;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Resource files compiled into the binary, used for any file missing from
+/// the runtime `mtgjson5/resources` directory
+macro_rules! embedded_resource {
+    ($file_name:literal) => {
+        (
+            $file_name,
+            include_str!(concat!("../../../mtgjson5/resources/", $file_name)),
+        )
+    };
+}
+
+const EMBEDDED_RESOURCES: &[(&str, &str)] = &[
+    embedded_resource!("additional_sets.json"),
+    embedded_resource!("base_set_sizes.json"),
+    embedded_resource!("booster_box_size_overrides.json"),
+    embedded_resource!("cardkingdom_sealed_name_mapping.json"),
+    embedded_resource!("frame_effects.json"),
+    embedded_resource!("gatherer_set_codes.json"),
+    embedded_resource!("keyrune_code_overrides.json"),
+    embedded_resource!("mkm_set_name_fixes.json"),
+    embedded_resource!("mkm_set_name_translations.json"),
+    embedded_resource!("multi_word_sub_types.json"),
+    embedded_resource!("sealed_name_fixes.json"),
+    embedded_resource!("set_block_overrides.json"),
+    embedded_resource!("set_code_watermarks.json"),
+    embedded_resource!("world_championship_signatures.json"),
+];
+
+/// The runtime `mtgjson5/resources` directory
+pub fn resource_dir() -> PathBuf {
+    std::env::current_dir()
+        .unwrap_or_else(|_| PathBuf::from("."))
+        .join("mtgjson5")
+        .join("resources")
+}
+
+/// Read a JSON resource from `mtgjson5/resources`
+pub fn load_resource<T: DeserializeOwned>(file_name: &str, fallback: impl FnOnce() -> T) -> T {
+    load_resource_from(&resource_dir(), file_name, fallback)
+}
+
+/// Read a JSON resource from `resource_dir`, falling back to the copy
+/// embedded at compile time when the file isn't there, and to `fallback`
+/// when neither can be used
+pub fn load_resource_from<T: DeserializeOwned>(
+    resource_dir: &Path,
+    file_name: &str,
+    fallback: impl FnOnce() -> T,
+) -> T {
+    let embedded = EMBEDDED_RESOURCES
+        .iter()
+        .find(|(name, _)| *name == file_name)
+        .map(|(_, content)| content.to_string());
+    let content = match (fs::read_to_string(resource_dir.join(file_name)), embedded) {
+        (Ok(content), _) | (Err(_), Some(content)) => content,
+        (Err(e), None) => {
+            eprintln!("Warning: Failed to read {}: {}", file_name, e);
+            return fallback();
+        }
+    };
+
+    serde_json::from_str(&content).unwrap_or_else(|e| {
+        eprintln!("Warning: Failed to parse {}: {}", file_name, e);
+        fallback()
+    })
+}
+
+/// Read a per-set JSON resource (e.g. `github_decks/C21.json`) that most
+/// sets don't have. `None` when the file is absent or can't be parsed.
+pub fn load_optional_resource<T: DeserializeOwned>(file_name: &str) -> Option<T> {
+    load_optional_resource_from(&resource_dir(), file_name)
+}
+
+/// Read an optional JSON resource from `resource_dir`, `None` when the
+/// file isn't there or can't be parsed
+pub fn load_optional_resource_from<T: DeserializeOwned>(
+    resource_dir: &Path,
+    file_name: &str,
+) -> Option<T> {
+    let content = fs::read_to_string(resource_dir.join(file_name)).ok()?;
+    serde_json::from_str(&content)
+        .map_err(|e| eprintln!("Warning: Failed to parse {}: {}", file_name, e))
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_embedded_resources_apply_without_runtime_dir() {
+        let missing_dir = std::env::temp_dir().join("mtgjson_no_resources_dir");
+        let _ = fs::remove_dir_all(&missing_dir);
+
+        let keyrune_overrides: HashMap<String, String> =
+            load_resource_from(&missing_dir, "keyrune_code_overrides.json", HashMap::new);
+        assert_eq!(
+            keyrune_overrides.get("DCI").map(String::as_str),
+            Some("PARL")
+        );
+        let sub_types: Vec<String> =
+            load_resource_from(&missing_dir, "multi_word_sub_types.json", Vec::new);
+        assert!(sub_types.iter().any(|sub_type| sub_type == "Time Lord"));
+
+        // A runtime copy takes precedence over the embedded one
+        let runtime_dir = std::env::temp_dir().join("mtgjson_runtime_resources_dir");
+        fs::create_dir_all(&runtime_dir).unwrap();
+        fs::write(
+            runtime_dir.join("keyrune_code_overrides.json"),
+            r#"{"DCI": "TEST"}"#,
+        )
+        .unwrap();
+        let keyrune_overrides: HashMap<String, String> =
+            load_resource_from(&runtime_dir, "keyrune_code_overrides.json", HashMap::new);
+        assert_eq!(
+            keyrune_overrides.get("DCI").map(String::as_str),
+            Some("TEST")
+        );
+        let _ = fs::remove_dir_all(&runtime_dir);
+    }
+
+    /// Resource files nothing in this crate reads. WizardsProvider only
+    /// downloads the comprehensive rules, so it has no set name lookups to
+    /// apply `wizards_set_name_fixes.json` to.
+    const UNREAD_RESOURCES: &[&str] = &["wizards_set_name_fixes.json"];
+
+    #[test]
+    fn test_every_resource_file_is_embedded() {
+        let resources_dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("mtgjson5")
+            .join("resources");
+        for entry in fs::read_dir(resources_dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            let file_name = path.file_name().unwrap().to_str().unwrap();
+            if UNREAD_RESOURCES.contains(&file_name) {
+                continue;
+            }
+            assert!(
+                EMBEDDED_RESOURCES
+                    .iter()
+                    .any(|(name, _)| *name == file_name),
+                "{} is not embedded",
+                file_name
+            );
+        }
+    }
+}
@@ -0,0 +1,69 @@
+// Shared Tokio runtime for the blocking bridges into async code
+use once_cell::sync::OnceCell;
+use std::future::Future;
+use tokio::runtime::{Handle, Runtime, RuntimeFlavor};
+
+static SHARED_RUNTIME: OnceCell<Runtime> = OnceCell::new();
+
+/// The process-wide runtime, started on first use
+pub fn shared_runtime() -> &'static Runtime {
+    SHARED_RUNTIME.get_or_init(|| {
+        tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .expect("Failed to start the shared Tokio runtime")
+    })
+}
+
+/// Run `future` to completion from synchronous code. Inside a multi-threaded
+/// runtime the current worker blocks in place; inside a current-thread
+/// runtime, which cannot block, the future runs on the shared runtime from a
+/// helper thread. Anywhere else it runs on the shared runtime directly.
+pub fn block_on_shared<F>(future: F) -> F::Output
+where
+    F: Future + Send,
+    F::Output: Send,
+{
+    match Handle::try_current() {
+        Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+            tokio::task::block_in_place(|| handle.block_on(future))
+        }
+        Ok(_) => std::thread::scope(|scope| {
+            scope
+                .spawn(|| shared_runtime().block_on(future))
+                .join()
+                .expect("Blocking bridge thread panicked")
+        }),
+        Err(_) => shared_runtime().block_on(future),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builders::set_builder::parse_printings;
+
+    #[test]
+    fn test_bridges_share_one_runtime() {
+        assert!(parse_printings(None).is_empty());
+        let runtime = shared_runtime() as *const Runtime;
+        assert!(parse_printings(None).is_empty());
+        assert_eq!(shared_runtime() as *const Runtime, runtime);
+
+        // Bridges called from async code reuse the caller's runtime
+        let nested = shared_runtime().block_on(async {
+            tokio::spawn(async { block_on_shared(async { parse_printings(None).len() }) })
+                .await
+                .unwrap()
+        });
+        assert_eq!(nested, 0);
+
+        let current_thread = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        assert_eq!(
+            current_thread.block_on(async { block_on_shared(async { 7 }) }),
+            7
+        );
+    }
+}
@@ -10,10 +10,8 @@ use crate::related_cards::MtgjsonRelatedCardsObject;
 use crate::rulings::MtgjsonRulingObject;
 use pyo3::prelude::*;
 
-use pyo3::types::PyDict;
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
-use std::ffi::CString;
 use std::collections::{HashMap, HashSet};
 
 /// MTGJSON Singular Card Object
@@ -274,6 +272,8 @@ pub struct MtgjsonCardObject {
     #[pyo3(get, set)]
     pub power: String,
 
+    // Prices are only published in AllPrices, never on set cards
+    #[serde(skip)]
     #[pyo3(get, set)]
     pub prices: MtgjsonPricesObject,
 
@@ -456,18 +456,7 @@ impl MtgjsonCardObject {
             original_type: None,
             other_face_ids: Vec::new(),
             power: String::new(),
-            prices: MtgjsonPricesObject::new(
-                String::new(),
-                String::new(),
-                String::new(),
-                "USD".to_string(),
-                None,
-                None,
-                None,
-                None,
-                None,
-                None,
-            ),
+            prices: MtgjsonPricesObject::default(),
             printings: Vec::new(),
             promo_types: Vec::new(),
             purchase_urls: MtgjsonPurchaseUrls::new(),
@@ -600,80 +589,14 @@ impl MtgjsonCardObject {
         ]
     }
 
-    /// Python equality method
+    /// Python equality method, the same as `==` in Rust
     pub fn __eq__(&self, other: &MtgjsonCardObject) -> bool {
-        self.number == other.number
-            && (self.side.as_deref().unwrap_or("") == other.side.as_deref().unwrap_or(""))
+        self == other
     }
 
     /// Python less-than comparison for sorting
-    /// Uses embedded Python logic to ensure 100% compatibility
     pub fn __lt__(&self, other: &MtgjsonCardObject) -> PyResult<bool> {
-        Python::with_gil(|py| {
-            // Keep your weird sorting then, Zach! lol
-            let python_code = r#"
-        def card_lt(self_number, self_side, other_number, other_side):
-            if self_number == other_number:
-                return (self_side or "") < (other_side or "")
-
-            self_side = self_side or ""
-            other_side = other_side or ""
-
-            self_number_clean = "".join(x for x in self_number if x.isdigit()) or "100000"
-            self_number_clean_int = int(self_number_clean)
-
-            other_number_clean = "".join(x for x in other_number if x.isdigit()) or "100000"
-            other_number_clean_int = int(other_number_clean)
-
-            # Check if both numbers are pure digits
-            self_is_digit = self_number == self_number_clean
-            other_is_digit = other_number == other_number_clean
-
-            if self_is_digit and other_is_digit:
-                if self_number_clean_int == other_number_clean_int:
-                    if len(self_number_clean) != len(other_number_clean):
-                        return len(self_number_clean) < len(other_number_clean)
-                    return self_side < other_side
-                return self_number_clean_int < other_number_clean_int
-
-            if self_is_digit:
-                if self_number_clean_int == other_number_clean_int:
-                    return True
-                return self_number_clean_int < other_number_clean_int
-
-            if other_is_digit:
-                if self_number_clean_int == other_number_clean_int:
-                    return False
-                return self_number_clean_int < other_number_clean_int
-
-            # Case 4: Neither is pure digit
-            # First check if digit strings are identical
-            if self_number_clean == other_number_clean:
-                if not self_side and not other_side:
-                    return self_number < other_number
-                return self_side < other_side
-
-            # Then check if integer values are the same but digit strings differ
-            if self_number_clean_int == other_number_clean_int:
-                if len(self_number_clean) != len(other_number_clean):
-                    return len(self_number_clean) < len(other_number_clean)
-                return self_side < other_side
-
-            return self_number_clean_int < other_number_clean_int
-
-        # Call the function
-        result = card_lt(self_number, self_side, other_number, other_side)
-        "#;
-            let locals = PyDict::new_bound(py);
-            locals.set_item("self_number", &self.number)?;
-            locals.set_item("self_side", &self.side)?;
-            locals.set_item("other_number", &other.number)?;
-            locals.set_item("other_side", &other.side)?;
-            let code_cstr = CString::new().unwrap();
-            py.run(code_cstr.as_c_str(), None, Some(&locals))?;
-            let result: bool = locals.get_item("result")?.unwrap().extract()?;
-            Ok(result)
-        })
+        Ok(self.cmp(other) == Ordering::Less)
     }
 
     /// Python string representation
@@ -723,28 +646,184 @@ impl Default for MtgjsonCardObject {
     }
 }
 
-impl PartialEq for MtgjsonCardObject {
-    fn eq(&self, other: &Self) -> bool {
-        self.__eq__(other)
+impl MtgjsonCardObject {
+    /// Start building a card with chainable setters
+    pub fn builder() -> MtgjsonCardBuilder {
+        MtgjsonCardBuilder::default()
+    }
+
+    /// A copy of this card with only its oracle-level fields, as used by
+    /// AtomicCards. Printing-specific fields such as the number, artist,
+    /// finishes, rarity, printing flags, prices and purchase URLs are cleared,
+    /// and of the identifiers only the Scryfall Oracle ID is kept.
+    pub fn to_atomic(&self) -> MtgjsonCardObject {
+        let mut atomic_card = MtgjsonCardObject::new(self.is_token);
+        atomic_card.identifiers.scryfall_oracle_id = self.identifiers.scryfall_oracle_id.clone();
+
+        atomic_card.ascii_name = self.ascii_name.clone();
+        atomic_card.color_identity = self.color_identity.clone();
+        atomic_card.color_indicator = self.color_indicator.clone();
+        atomic_card.colors = self.colors.clone();
+        atomic_card.converted_mana_cost = self.converted_mana_cost;
+        atomic_card.defense = self.defense.clone();
+        atomic_card.edhrec_rank = self.edhrec_rank;
+        atomic_card.edhrec_saltiness = self.edhrec_saltiness;
+        atomic_card.face_converted_mana_cost = self.face_converted_mana_cost;
+        atomic_card.face_mana_value = self.face_mana_value;
+        atomic_card.face_name = self.face_name.clone();
+        atomic_card.first_printing = self.first_printing.clone();
+        atomic_card.foreign_data = self.foreign_data.clone();
+        atomic_card.hand = self.hand.clone();
+        atomic_card.has_alternative_deck_limit = self.has_alternative_deck_limit;
+        atomic_card.is_funny = self.is_funny;
+        atomic_card.is_reserved = self.is_reserved;
+        atomic_card.keywords = self.keywords.clone();
+        atomic_card.layout = self.layout.clone();
+        atomic_card.leadership_skills = self.leadership_skills.clone();
+        atomic_card.legalities = self.legalities.clone();
+        atomic_card.life = self.life.clone();
+        atomic_card.loyalty = self.loyalty.clone();
+        atomic_card.mana_cost = self.mana_cost.clone();
+        atomic_card.mana_value = self.mana_value;
+        atomic_card.name = self.name.clone();
+        atomic_card.power = self.power.clone();
+        atomic_card.printings = self.printings.clone();
+        atomic_card.rulings = self.rulings.clone();
+        atomic_card.side = self.side.clone();
+        atomic_card.subtypes = self.subtypes.clone();
+        atomic_card.supertypes = self.supertypes.clone();
+        atomic_card.text = self.text.clone();
+        atomic_card.toughness = self.toughness.clone();
+        atomic_card.type_ = self.type_.clone();
+        atomic_card.types = self.types.clone();
+
+        atomic_card
+    }
+
+    /// Frame effects on this card that are not in `known_frame_effects`
+    pub fn unknown_frame_effects(&self, known_frame_effects: &[String]) -> Vec<String> {
+        self.frame_effects
+            .iter()
+            .filter(|frame_effect| !known_frame_effects.contains(frame_effect))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Chainable constructor for the commonly populated card fields
+#[derive(Debug, Clone, Default)]
+pub struct MtgjsonCardBuilder {
+    card: MtgjsonCardObject,
+}
+
+impl MtgjsonCardBuilder {
+    pub fn is_token(mut self, is_token: bool) -> Self {
+        self.card.is_token = is_token;
+        self
+    }
+
+    pub fn name(mut self, name: &str) -> Self {
+        self.card.name = name.to_string();
+        self
+    }
+
+    pub fn set_code(mut self, set_code: &str) -> Self {
+        self.card.set_code = set_code.to_uppercase();
+        self
+    }
+
+    pub fn number(mut self, number: &str) -> Self {
+        self.card.number = number.to_string();
+        self
+    }
+
+    /// Sets the type line, along with the parsed super/sub types
+    pub fn type_(mut self, type_line: &str) -> Self {
+        let (supertypes, types, subtypes) =
+            crate::builders::set_builder::parse_card_types(type_line);
+        self.card.type_ = type_line.to_string();
+        self.card.supertypes = supertypes;
+        self.card.types = types;
+        self.card.subtypes = subtypes;
+        self
+    }
+
+    /// Sets the mana cost, along with the derived mana value
+    pub fn mana_cost(mut self, mana_cost: &str) -> Self {
+        self.card.mana_cost = mana_cost.to_string();
+        self.card.mana_value = crate::builders::set_builder::get_card_cmc(mana_cost);
+        self.card.converted_mana_cost = self.card.mana_value;
+        self
+    }
+
+    pub fn colors(mut self, colors: &[&str]) -> Self {
+        self.card.colors = colors.iter().map(|c| c.to_string()).collect();
+        self
+    }
+
+    pub fn rarity(mut self, rarity: &str) -> Self {
+        self.card.rarity = rarity.to_string();
+        self
+    }
+
+    pub fn scryfall_id(mut self, scryfall_id: &str) -> Self {
+        self.card.identifiers.scryfall_id = Some(scryfall_id.to_string());
+        self
+    }
+
+    /// Finish the card, assigning its UUID
+    pub fn build(mut self) -> MtgjsonCardObject {
+        crate::builders::set_builder::add_uuid(&mut self.card);
+        self.card
+    }
+}
+
+/// Sort key for a collector number: the value of its digits ("100000" when
+/// it has none), pure numbers ahead of lettered ones, fewer digits first,
+/// then the number itself so every collector number sorts deterministically
+fn collector_number_key(number: &str) -> (u64, bool, usize, &str) {
+    let digits: String = number.chars().filter(|c| c.is_ascii_digit()).collect();
+    let digits = if digits.is_empty() {
+        "100000".to_string()
+    } else {
+        digits
+    };
+    (
+        digits.parse().unwrap_or(u64::MAX),
+        number != digits,
+        digits.len(),
+        number,
+    )
+}
+
+/// Cards order by collector number, then name, then side
+impl Ord for MtgjsonCardObject {
+    fn cmp(&self, other: &Self) -> Ordering {
+        collector_number_key(&self.number)
+            .cmp(&collector_number_key(&other.number))
+            .then_with(|| self.name.cmp(&other.name))
+            .then_with(|| {
+                let self_side = self.side.as_deref().unwrap_or("");
+                let other_side = other.side.as_deref().unwrap_or("");
+                self_side.cmp(other_side)
+            })
     }
 }
 
 impl PartialOrd for MtgjsonCardObject {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        // This implementation is not used by PyO3 comparison operators
-        // The actual comparison is done in __lt__ method using embedded Python
-        match self.__lt__(other) {
-            Ok(true) => Some(Ordering::Less),
-            Ok(false) => match other.__lt__(self) {
-                Ok(true) => Some(Ordering::Greater),
-                Ok(false) => Some(Ordering::Equal),
-                Err(_) => None,
-            },
-            Err(_) => None,
-        }
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for MtgjsonCardObject {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
     }
 }
 
+impl Eq for MtgjsonCardObject {}
+
 impl JsonObject for MtgjsonCardObject {
     fn build_keys_to_skip(&self) -> HashSet<String> {
         let mut excluded_keys = HashSet::new();
@@ -1098,6 +1177,58 @@ mod tests {
         assert_eq!(cards[2].number, "10");
     }
 
+    #[test]
+    fn test_python_equality_matches_rust_equality() {
+        let mut card1 = MtgjsonCardObject::new(false);
+        card1.name = "Forest".to_string();
+        card1.number = "1".to_string();
+        let mut card2 = card1.clone();
+        assert!(card1.__eq__(&card2));
+        assert_eq!(card1.__eq__(&card2), card1 == card2);
+
+        card2.name = "Island".to_string();
+        assert!(!card1.__eq__(&card2));
+        assert_eq!(card1.__eq__(&card2), card1 == card2);
+    }
+
+    #[test]
+    fn test_card_ord_sorts_numbers_numerically_then_side() {
+        let card = |number: &str, side: Option<&str>| {
+            let mut card = MtgjsonCardObject::new(false);
+            card.name = "Test".to_string();
+            card.number = number.to_string();
+            card.side = side.map(str::to_string);
+            card
+        };
+
+        let mut cards = vec![
+            card("★", None),
+            card("10", None),
+            card("12a", None),
+            card("2", Some("b")),
+            card("12", None),
+            card("2", Some("a")),
+        ];
+        cards.sort();
+
+        let order: Vec<(&str, Option<&str>)> = cards
+            .iter()
+            .map(|c| (c.number.as_str(), c.side.as_deref()))
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                ("2", Some("a")),
+                ("2", Some("b")),
+                ("10", None),
+                ("12", None),
+                ("12a", None),
+                ("★", None),
+            ]
+        );
+        assert!(card("2", None) < card("10", None));
+    }
+
     // COMPREHENSIVE ADDITIONAL TESTS FOR FULL COVERAGE
 
     #[test]
@@ -1396,4 +1527,91 @@ mod tests {
         assert!(json_str.contains("Planeswalker"));
         assert!(json_str.contains("Jason Chan"));
     }
+
+    #[test]
+    fn test_card_builder() {
+        let card = MtgjsonCardObject::builder()
+            .name("Llanowar Elves")
+            .set_code("dom")
+            .number("168")
+            .type_("Creature — Elf Druid")
+            .mana_cost("{G}")
+            .colors(&["G"])
+            .rarity("common")
+            .scryfall_id("0a1b2c3d-0000-4000-8000-000000000168")
+            .build();
+
+        assert_eq!(card.name, "Llanowar Elves");
+        assert_eq!(card.set_code, "DOM");
+        assert_eq!(card.number, "168");
+        assert_eq!(card.types, vec!["Creature"]);
+        assert_eq!(card.subtypes, vec!["Elf", "Druid"]);
+        assert_eq!(card.mana_value, 1.0);
+        assert_eq!(card.colors, vec!["G"]);
+        assert_eq!(card.rarity, "common");
+        assert!(!card.is_token);
+
+        // UUIDs are deterministic for the same inputs
+        assert!(!card.uuid.is_empty());
+        assert!(card.identifiers.mtgjson_v4_id.is_some());
+        let again = MtgjsonCardObject::builder()
+            .name("Llanowar Elves")
+            .set_code("DOM")
+            .scryfall_id("0a1b2c3d-0000-4000-8000-000000000168")
+            .build();
+        assert_eq!(card.uuid, again.uuid);
+    }
+
+    #[test]
+    fn test_to_atomic_clears_printing_fields() {
+        let mut card = MtgjsonCardObject::builder()
+            .name("Llanowar Elves")
+            .set_code("DOM")
+            .number("168")
+            .type_("Creature — Elf Druid")
+            .mana_cost("{G}")
+            .colors(&["G"])
+            .rarity("common")
+            .scryfall_id("0a1b2c3d-0000-4000-8000-000000000168")
+            .build();
+        card.artist = "Chris Rahn".to_string();
+        card.finishes = vec!["nonfoil".to_string(), "foil".to_string()];
+        card.is_promo = Some(true);
+        card.is_reprint = Some(true);
+        card.text = "{T}: Add {G}.".to_string();
+        card.power = "1".to_string();
+        card.toughness = "1".to_string();
+        card.identifiers.scryfall_oracle_id = Some("oracle-elves".to_string());
+        card.edhrec_rank = Some(412);
+        card.prices.sell_normal = Some(0.25);
+        card.purchase_urls.tcgplayer = Some("https://mtgjson.com/links/elves".to_string());
+
+        let atomic_card = card.to_atomic();
+
+        assert!(atomic_card.number.is_empty());
+        assert!(atomic_card.artist.is_empty());
+        assert!(atomic_card.finishes.is_empty());
+        assert!(atomic_card.rarity.is_empty());
+        assert_eq!(atomic_card.is_promo, None);
+        assert_eq!(atomic_card.is_reprint, None);
+        assert!(atomic_card.uuid.is_empty());
+        assert_eq!(atomic_card.identifiers.scryfall_id, None);
+        assert_eq!(atomic_card.identifiers.mtgjson_v4_id, None);
+        assert_eq!(atomic_card.prices.sell_normal, None);
+        assert_eq!(atomic_card.purchase_urls.tcgplayer, None);
+
+        assert_eq!(atomic_card.name, "Llanowar Elves");
+        assert_eq!(atomic_card.text, "{T}: Add {G}.");
+        assert_eq!(atomic_card.mana_cost, "{G}");
+        assert_eq!(atomic_card.mana_value, 1.0);
+        assert_eq!(atomic_card.colors, vec!["G"]);
+        assert_eq!(atomic_card.subtypes, vec!["Elf", "Druid"]);
+        assert_eq!(atomic_card.power, "1");
+        assert_eq!(atomic_card.toughness, "1");
+        assert_eq!(atomic_card.edhrec_rank, Some(412));
+        assert_eq!(
+            atomic_card.identifiers.scryfall_oracle_id.as_deref(),
+            Some("oracle-elves")
+        );
+    }
 }
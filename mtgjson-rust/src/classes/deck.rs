@@ -245,7 +245,6 @@ pub struct MtgjsonDeckHeaderObject {
     #[pyo3(get, set)]
     pub code: String,
 
-    #[serde(skip)]
     #[pyo3(get, set)]
     pub file_name: String,
 
@@ -3,6 +3,9 @@ use pyo3::prelude::*;
 use serde::{Deserialize, Serialize};
 
 /// MTGJSON Singular Card.LeadershipSkills Object
+///
+/// All three flags are always serialized, including the false ones, so
+/// consumers can rely on every key being present.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[pyclass(name = "MtgjsonLeadershipSkillsObject")]
 pub struct MtgjsonLeadershipSkillsObject {
@@ -17,7 +17,7 @@ pub mod translations;
 pub mod utils;
 
 pub use base::JsonObject;
-pub use card::MtgjsonCardObject;
+pub use card::{MtgjsonCardBuilder, MtgjsonCardObject};
 pub use deck::{MtgjsonDeckHeaderObject, MtgjsonDeckObject};
 pub use foreign_data::MtgjsonForeignDataObject;
 pub use game_formats::MtgjsonGameFormatsObject;
@@ -1,8 +1,29 @@
 use crate::base::{skip_if_empty_optional_string, JsonObject};
 use pyo3::prelude::*;
 use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
 use std::collections::HashMap;
 
+/// Price types in the order they appear in AllPrices
+pub const PRICE_TYPES: [&str; 2] = ["buylist", "retail"];
+
+/// Card finishes a price can be listed for
+pub const PRICE_FINISHES: [&str; 3] = ["normal", "foil", "etched"];
+
+/// Field on `MtgjsonPricesObject` that holds the `price_type` price for
+/// `finish`, e.g. `("buylist", "foil")` is `buy_foil`
+pub fn get_price_field_name(price_type: &str, finish: &str) -> Option<&'static str> {
+    match (price_type, finish) {
+        ("buylist", "normal") => Some("buy_normal"),
+        ("buylist", "foil") => Some("buy_foil"),
+        ("buylist", "etched") => Some("buy_etched"),
+        ("retail", "normal") => Some("sell_normal"),
+        ("retail", "foil") => Some("sell_foil"),
+        ("retail", "etched") => Some("sell_etched"),
+        _ => None,
+    }
+}
+
 /// MTGJSON Singular Prices.Card Object
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[pyclass(name = "MtgjsonPricesObject")]
@@ -170,12 +191,100 @@ impl MtgjsonPricesObject {
     }
 }
 
+impl MtgjsonPricesObject {
+    /// Price held in the field named by `get_price_field_name`
+    pub fn get_price(&self, field_name: &str) -> Option<f64> {
+        match field_name {
+            "buy_normal" => self.buy_normal,
+            "buy_foil" => self.buy_foil,
+            "buy_etched" => self.buy_etched,
+            "sell_normal" => self.sell_normal,
+            "sell_foil" => self.sell_foil,
+            "sell_etched" => self.sell_etched,
+            _ => None,
+        }
+    }
+
+    /// This entry in the AllPrices layout:
+    /// `{source: {provider: {buylist/retail: {finish: {date: price}}, currency}}}`
+    pub fn to_price_entry(&self) -> Value {
+        let mut provider_prices = Map::new();
+        for price_type in PRICE_TYPES {
+            let mut finishes = Map::new();
+            for finish in PRICE_FINISHES {
+                let price = get_price_field_name(price_type, finish)
+                    .and_then(|field_name| self.get_price(field_name));
+                if let Some(price) = price {
+                    finishes.insert(finish.to_string(), json!({ self.date.clone(): price }));
+                }
+            }
+            if !finishes.is_empty() {
+                provider_prices.insert(price_type.to_string(), Value::Object(finishes));
+            }
+        }
+        provider_prices.insert("currency".to_string(), json!(self.currency));
+
+        json!({ self.source.clone(): { self.provider.clone(): provider_prices } })
+    }
+}
+
+impl Default for MtgjsonPricesObject {
+    fn default() -> Self {
+        Self::new(
+            String::new(),
+            String::new(),
+            String::new(),
+            "USD".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+    }
+}
+
 impl JsonObject for MtgjsonPricesObject {}
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_price_entry_routes_fields_by_type_and_finish() {
+        let prices = MtgjsonPricesObject::new(
+            "paper".to_string(),
+            "cardkingdom".to_string(),
+            "2024-01-15".to_string(),
+            "USD".to_string(),
+            Some(1.5),
+            None,
+            None,
+            Some(3.0),
+            Some(7.25),
+            None,
+        );
+
+        assert_eq!(get_price_field_name("retail", "foil"), Some("sell_foil"));
+        assert_eq!(
+            get_price_field_name("buylist", "etched"),
+            Some("buy_etched")
+        );
+        assert_eq!(get_price_field_name("retail", "glossy"), None);
+        assert_eq!(
+            prices.to_price_entry(),
+            json!({"paper": {"cardkingdom": {
+                "buylist": {"normal": {"2024-01-15": 1.5}},
+                "retail": {
+                    "normal": {"2024-01-15": 3.0},
+                    "foil": {"2024-01-15": 7.25}
+                },
+                "currency": "USD"
+            }}})
+        );
+    }
+
     #[test]
     fn test_prices_creation() {
         let prices = MtgjsonPricesObject::new(
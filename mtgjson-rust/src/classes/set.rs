@@ -16,6 +16,10 @@ pub struct MtgjsonSetObject {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub base_set_size: Option<i32>,
 
+    #[pyo3(get, set)]
+    #[serde(skip_serializing_if = "skip_if_empty_optional_string")]
+    pub block: Option<String>,
+
     #[pyo3(get, set)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub booster: Option<String>,
@@ -130,6 +134,7 @@ impl MtgjsonSetObject {
     pub fn new() -> Self {
         Self {
             base_set_size: None,
+            block: None,
             booster: None,
             cards: Vec::new(),
             cardsphere_set_id: None,
@@ -350,7 +355,8 @@ impl MtgjsonSetObject {
         );
     }
 
-    /// Validate set integrity
+    /// Validate set integrity, returning every invariant violation found.
+    /// An empty list means the set is valid.
     pub fn validate(&self) -> Vec<String> {
         let mut errors = Vec::new();
 
@@ -370,6 +376,21 @@ impl MtgjsonSetObject {
             errors.push("Set type is required".to_string());
         }
 
+        if let Some(base_set_size) = self.base_set_size {
+            if base_set_size > self.total_set_size {
+                errors.push(format!(
+                    "Base set size {} exceeds total set size {}",
+                    base_set_size, self.total_set_size
+                ));
+            }
+        }
+
+        for card in self.cards.iter().chain(self.tokens.iter()) {
+            if card.name.is_empty() {
+                errors.push(format!("Card name is required: {}", card.uuid));
+            }
+        }
+
         // Check for duplicate card UUIDs
         let mut seen_uuids = HashSet::new();
         for card in &self.cards {
@@ -385,6 +406,26 @@ impl MtgjsonSetObject {
             }
         }
 
+        // Check that face and variation references point at cards in this set
+        for card in self.cards.iter().chain(self.tokens.iter()) {
+            for other_face_id in &card.other_face_ids {
+                if !seen_uuids.contains(other_face_id) {
+                    errors.push(format!(
+                        "Dangling otherFaceId {} on card {}",
+                        other_face_id, card.uuid
+                    ));
+                }
+            }
+            for variation in &card.variations {
+                if !seen_uuids.contains(variation) {
+                    errors.push(format!(
+                        "Dangling variation {} on card {}",
+                        variation, card.uuid
+                    ));
+                }
+            }
+        }
+
         errors
     }
 }
@@ -414,6 +455,9 @@ impl JsonObject for MtgjsonSetObject {
         ];
 
         // Skip empty values that aren't in the allow list
+        if self.block.is_none() {
+            excluded_keys.insert("block".to_string());
+        }
         if self.booster.is_none() {
             excluded_keys.insert("booster".to_string());
         }
@@ -763,6 +807,66 @@ mod tests {
             .any(|e| e.contains("Duplicate card UUID found")));
     }
 
+    #[test]
+    fn test_validate_broken_set_reports_violations() {
+        let mut set = MtgjsonSetObject::new();
+        set.code = Some("TST".to_string());
+        set.name = "Test Set".to_string();
+        set.release_date = "2023-01-01".to_string();
+        set.type_ = "expansion".to_string();
+        set.base_set_size = Some(10);
+        set.total_set_size = 2;
+
+        let mut front = MtgjsonCardObject::new(false);
+        front.name = "Front".to_string();
+        front.uuid = "uuid-1".to_string();
+        front.other_face_ids = vec!["missing-face".to_string()];
+        front.variations = vec!["missing-variation".to_string()];
+
+        let mut unnamed = MtgjsonCardObject::new(false);
+        unnamed.uuid = "uuid-2".to_string();
+
+        set.add_card(front);
+        set.add_card(unnamed);
+
+        assert_eq!(
+            set.validate(),
+            vec![
+                "Base set size 10 exceeds total set size 2",
+                "Card name is required: uuid-2",
+                "Dangling otherFaceId missing-face on card uuid-1",
+                "Dangling variation missing-variation on card uuid-1",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_clean_set_with_references() {
+        let mut set = MtgjsonSetObject::new();
+        set.code = Some("TST".to_string());
+        set.name = "Test Set".to_string();
+        set.release_date = "2023-01-01".to_string();
+        set.type_ = "expansion".to_string();
+        set.base_set_size = Some(2);
+        set.total_set_size = 2;
+
+        let mut front = MtgjsonCardObject::new(false);
+        front.name = "Front".to_string();
+        front.uuid = "uuid-1".to_string();
+        front.other_face_ids = vec!["uuid-2".to_string()];
+
+        let mut back = MtgjsonCardObject::new(false);
+        back.name = "Back".to_string();
+        back.uuid = "uuid-2".to_string();
+        back.other_face_ids = vec!["uuid-1".to_string()];
+        back.variations = vec!["uuid-1".to_string()];
+
+        set.add_card(front);
+        set.add_card(back);
+
+        assert!(set.validate().is_empty());
+    }
+
     #[test]
     fn test_get_windows_safe_set_code() {
         let mut set = MtgjsonSetObject::new();
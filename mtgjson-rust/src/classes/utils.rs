@@ -52,6 +52,12 @@ impl MtgjsonUtils {
             .collect::<String>()
             .to_lowercase()
     }
+
+    /// Generate a name-based UUIDv5 in the DNS namespace, matching
+    /// Python's `uuid.uuid5(uuid.NAMESPACE_DNS, name)`
+    pub fn uuid5_dns(name: &str) -> String {
+        uuid::Uuid::new_v5(&uuid::Uuid::NAMESPACE_DNS, name.as_bytes()).to_string()
+    }
 }
 
 #[cfg(test)]
@@ -82,4 +88,13 @@ mod tests {
         let result = MtgjsonUtils::alpha_numeric_only("Test-Deck! 123");
         assert_eq!(result, "testdeck 123");
     }
+
+    #[test]
+    fn test_uuid5_dns_matches_python() {
+        // uuid.uuid5(uuid.NAMESPACE_DNS, "Black Lotus")
+        assert_eq!(
+            MtgjsonUtils::uuid5_dns("Black Lotus"),
+            "288bc0d8-1ee9-5af5-b226-38aec9cc1c5d"
+        );
+    }
 }
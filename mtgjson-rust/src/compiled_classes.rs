@@ -12,7 +12,7 @@ pub mod structures;
 pub mod tcgplayer_skus;
 
 // Re-export main types
-pub use all_identifiers::MtgjsonAllIdentifiers;
+pub use all_identifiers::{build_all_identifiers, MtgjsonAllIdentifiers};
 pub use all_printings::MtgjsonAllPrintings;
 pub use atomic_cards::MtgjsonAtomicCards;
 pub use card_types::MtgjsonCardTypesObject;
@@ -20,6 +20,6 @@ pub use compiled_list::MtgjsonCompiledList;
 pub use deck_list::MtgjsonDeckObjectList;
 pub use enum_values::MtgjsonEnumValues;
 pub use keywords::MtgjsonKeywords;
-pub use set_list::MtgjsonSetObjectList;
+pub use set_list::{MtgjsonSetListEntry, MtgjsonSetObjectList};
 pub use structures::MtgjsonStructures;
-pub use tcgplayer_skus::MtgjsonTcgplayerSkus;
+pub use tcgplayer_skus::{MtgjsonTcgplayerSku, MtgjsonTcgplayerSkus};
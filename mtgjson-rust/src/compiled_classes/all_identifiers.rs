@@ -1,4 +1,5 @@
 use crate::classes::{JsonObject, MtgjsonCardObject, MtgjsonSetObject};
+use crate::compiled_classes::MtgjsonAllPrintings;
 use pyo3::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -10,6 +11,32 @@ use std::collections::HashMap;
 pub struct MtgjsonAllIdentifiers {
     #[pyo3(get, set)]
     pub all_identifiers_dict: HashMap<String, MtgjsonCardObject>,
+
+    /// Scryfall ID -> MTGJSON UUID
+    #[serde(skip)]
+    #[pyo3(get)]
+    pub scryfall_id_index: HashMap<String, String>,
+
+    /// Multiverse ID -> MTGJSON UUID
+    #[serde(skip)]
+    #[pyo3(get)]
+    pub multiverse_id_index: HashMap<String, String>,
+
+    /// MTGO ID -> MTGJSON UUID
+    #[serde(skip)]
+    #[pyo3(get)]
+    pub mtgo_id_index: HashMap<String, String>,
+
+    /// Cardmarket ID -> MTGJSON UUID
+    #[serde(skip)]
+    #[pyo3(get)]
+    pub mcm_id_index: HashMap<String, String>,
+
+    /// Source identifiers found on more than one printing, as
+    /// "<identifier> <value>: <indexed UUID>, <duplicate UUID>"
+    #[serde(skip)]
+    #[pyo3(get)]
+    pub duplicate_identifiers: Vec<String>,
 }
 
 #[pymethods]
@@ -18,6 +45,11 @@ impl MtgjsonAllIdentifiers {
     pub fn new() -> Self {
         Self {
             all_identifiers_dict: HashMap::new(),
+            scryfall_id_index: HashMap::new(),
+            multiverse_id_index: HashMap::new(),
+            mtgo_id_index: HashMap::new(),
+            mcm_id_index: HashMap::new(),
+            duplicate_identifiers: Vec::new(),
         }
     }
 
@@ -64,6 +96,7 @@ impl MtgjsonAllIdentifiers {
 
         Ok(Self {
             all_identifiers_dict,
+            ..Self::new()
         })
     }
 
@@ -221,6 +254,93 @@ impl MtgjsonAllIdentifiers {
     }
 }
 
+impl MtgjsonAllIdentifiers {
+    /// Point each of `card`'s source identifiers at its UUID. An identifier
+    /// already pointing at another printing keeps it and is recorded as a
+    /// duplicate; faces of one printing share identifiers and are not.
+    fn index_card(&mut self, card: &MtgjsonCardObject) {
+        let identifiers = &card.identifiers;
+        let indexes = [
+            (
+                "scryfallId",
+                &identifiers.scryfall_id,
+                &mut self.scryfall_id_index,
+            ),
+            (
+                "multiverseId",
+                &identifiers.multiverse_id,
+                &mut self.multiverse_id_index,
+            ),
+            ("mtgoId", &identifiers.mtgo_id, &mut self.mtgo_id_index),
+            ("mcmId", &identifiers.mcm_id, &mut self.mcm_id_index),
+        ];
+
+        for (identifier, value, index) in indexes {
+            let Some(value) = value.as_deref().filter(|value| !value.is_empty()) else {
+                continue;
+            };
+            match index.get(value) {
+                None => {
+                    index.insert(value.to_string(), card.uuid.clone());
+                }
+                Some(indexed_uuid) => {
+                    let same_printing =
+                        self.all_identifiers_dict
+                            .get(indexed_uuid)
+                            .is_some_and(|indexed_card| {
+                                indexed_card.set_code == card.set_code
+                                    && indexed_card.number == card.number
+                            });
+                    if !same_printing {
+                        let duplicate =
+                            format!("{} {}: {}, {}", identifier, value, indexed_uuid, card.uuid);
+                        eprintln!("Warning: Duplicate source identifier {}", duplicate);
+                        self.duplicate_identifiers.push(duplicate);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Build AllIdentifiers from every card and token in AllPrintings, along
+/// with the Scryfall, Multiverse, MTGO and Cardmarket ID indexes
+pub fn build_all_identifiers(all_printings: &MtgjsonAllPrintings) -> MtgjsonAllIdentifiers {
+    let mut all_identifiers = MtgjsonAllIdentifiers::new();
+
+    // Walk sets in a stable order so the first printing of a duplicated
+    // identifier is always the one indexed
+    let mut set_codes: Vec<&String> = all_printings.all_sets_dict.keys().collect();
+    set_codes.sort();
+    for set_code in set_codes {
+        let mtgjson_set = &all_printings.all_sets_dict[set_code];
+        for card in mtgjson_set.cards.iter().chain(&mtgjson_set.tokens) {
+            if all_identifiers
+                .all_identifiers_dict
+                .contains_key(&card.uuid)
+            {
+                eprintln!(
+                    "Duplicate MTGJSON UUID {} detected for card: {}",
+                    card.uuid, card.name
+                );
+                continue;
+            }
+            all_identifiers.index_card(card);
+            all_identifiers
+                .all_identifiers_dict
+                .insert(card.uuid.clone(), card.clone());
+        }
+    }
+
+    if !all_identifiers.duplicate_identifiers.is_empty() {
+        eprintln!(
+            "Found {} duplicate source identifiers during AllIdentifiers creation",
+            all_identifiers.duplicate_identifiers.len()
+        );
+    }
+    all_identifiers
+}
+
 impl Default for MtgjsonAllIdentifiers {
     fn default() -> Self {
         Self::new()
@@ -284,6 +404,50 @@ mod tests {
         assert_eq!(lea_cards[0], 0);
     }
 
+    #[test]
+    fn test_build_all_identifiers_indexes_and_flags_duplicates() {
+        let mut first = create_test_card("uuid1", "Lightning Bolt", "LEA");
+        first.number = "161".to_string();
+        first.identifiers.scryfall_id = Some("sf-bolt".to_string());
+        first.identifiers.multiverse_id = Some("209".to_string());
+        let mut second = create_test_card("uuid2", "Counterspell", "LEA");
+        second.number = "54".to_string();
+        second.identifiers.scryfall_id = Some("sf-counterspell".to_string());
+        second.identifiers.mtgo_id = Some("1234".to_string());
+        second.identifiers.mcm_id = Some("5678".to_string());
+        let mut duplicate = create_test_card("uuid3", "Lightning Bolt", "LEB");
+        duplicate.number = "162".to_string();
+        duplicate.identifiers.scryfall_id = Some("sf-bolt".to_string());
+
+        let mut all_printings = MtgjsonAllPrintings::new();
+        for (code, cards) in [("LEA", vec![first, second]), ("LEB", vec![duplicate])] {
+            let mut mtgjson_set = MtgjsonSetObject::new();
+            mtgjson_set.code = Some(code.to_string());
+            mtgjson_set.cards = cards;
+            all_printings
+                .all_sets_dict
+                .insert(code.to_string(), mtgjson_set);
+        }
+
+        let all_identifiers = build_all_identifiers(&all_printings);
+
+        assert_eq!(all_identifiers.card_count(), 3);
+        assert_eq!(
+            all_identifiers.scryfall_id_index["sf-counterspell"],
+            "uuid2"
+        );
+        assert_eq!(all_identifiers.multiverse_id_index["209"], "uuid1");
+        assert_eq!(all_identifiers.mtgo_id_index["1234"], "uuid2");
+        assert_eq!(all_identifiers.mcm_id_index["5678"], "uuid2");
+
+        // The duplicate is reported and the first printing keeps the ID
+        assert_eq!(all_identifiers.scryfall_id_index["sf-bolt"], "uuid1");
+        assert_eq!(
+            all_identifiers.duplicate_identifiers,
+            vec!["scryfallId sf-bolt: uuid1, uuid3"]
+        );
+    }
+
     #[test]
     fn test_statistics() {
         let mut all_identifiers = MtgjsonAllIdentifiers::new();
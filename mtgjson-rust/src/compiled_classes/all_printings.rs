@@ -1,10 +1,26 @@
-use crate::classes::{JsonObject, MtgjsonSetObject};
+use crate::classes::{JsonObject, MtgjsonCardObject, MtgjsonSetObject};
 use pyo3::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::Path;
 
+/// Whether a JSON file stem names an individual set file (`DOM`, `CON_`)
+/// rather than a compiled output (`AllPrintings`) or a `.pretty` copy
+pub fn is_set_file_name(file_stem: &str) -> bool {
+    !file_stem.is_empty()
+        && !file_stem.contains('.')
+        && !file_stem.chars().any(|c| c.is_lowercase())
+}
+
+/// Cards are the same card across sets when they share an oracle id
+fn printing_key(card: &MtgjsonCardObject) -> String {
+    card.identifiers
+        .scryfall_oracle_id
+        .clone()
+        .unwrap_or_else(|| card.name.clone())
+}
+
 /// MTGJSON AllPrintings Object
 /// Rust equivalent of MtgjsonAllPrintingsObject
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -114,6 +130,12 @@ impl MtgjsonAllPrintings {
             let path = entry.path();
             if path.extension().and_then(|s| s.to_str()) == Some("json") {
                 if let Some(file_name) = path.file_stem().and_then(|s| s.to_str()) {
+                    // Compiled outputs (AllPrintings, Meta, ...) and pretty
+                    // copies share the directory; only set files are all caps
+                    if !is_set_file_name(file_name) {
+                        continue;
+                    }
+
                     // CON filename fix for Windows
                     let set_code = if file_name == "CON_" {
                         "CON".to_string()
@@ -145,14 +167,25 @@ impl MtgjsonAllPrintings {
             ))
         })?;
 
-        let mtgjson_set: MtgjsonSetObject = serde_json::from_str(&contents).map_err(|e| {
+        let mut json_value: serde_json::Value = serde_json::from_str(&contents).map_err(|e| {
             PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
                 "Invalid JSON in {}: {}",
                 file_path, e
             ))
         })?;
 
-        Ok(mtgjson_set)
+        // Set files are written wrapped in a meta structure
+        let set_data = match json_value.get_mut("data") {
+            Some(data) => data.take(),
+            None => json_value,
+        };
+
+        serde_json::from_value(set_data).map_err(|e| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Invalid set in {}: {}",
+                file_path, e
+            ))
+        })
     }
 
     /// Get set contents like Python method
@@ -195,6 +228,54 @@ impl MtgjsonAllPrintings {
         true
     }
 
+    /// Post-compile pass: point each card printed in more than one set at
+    /// the earliest released of them (ties going to the lower set code),
+    /// and reconcile `is_reprint` so only printings in that set are not
+    /// reprints, overriding Scryfall's per-printing value
+    pub fn add_first_printings(&mut self) {
+        // Printing key -> (release date, set code) of the earliest printing
+        let mut first_printings: HashMap<String, (String, String)> = HashMap::new();
+        // Printing key -> every set it was printed in
+        let mut printing_sets: HashMap<String, HashSet<String>> = HashMap::new();
+        for (set_code, set_data) in &self.all_sets_dict {
+            let release_date = if set_data.release_date.is_empty() {
+                "9999-12-31".to_string()
+            } else {
+                set_data.release_date.clone()
+            };
+            let set_code = set_data.code.clone().unwrap_or_else(|| set_code.clone());
+
+            for card in &set_data.cards {
+                let candidate = (release_date.clone(), set_code.clone());
+                printing_sets
+                    .entry(printing_key(card))
+                    .or_default()
+                    .insert(set_code.clone());
+                first_printings
+                    .entry(printing_key(card))
+                    .and_modify(|earliest| {
+                        if candidate < *earliest {
+                            *earliest = candidate.clone();
+                        }
+                    })
+                    .or_insert(candidate);
+            }
+        }
+
+        for (set_code, set_data) in self.all_sets_dict.iter_mut() {
+            let set_code = set_data.code.clone().unwrap_or_else(|| set_code.clone());
+            for card in set_data.cards.iter_mut() {
+                let key = printing_key(card);
+                if let Some((_, first_set_code)) = first_printings.get(&key) {
+                    if printing_sets.get(&key).map_or(0, HashSet::len) > 1 {
+                        card.first_printing = Some(first_set_code.clone());
+                    }
+                    card.is_reprint = Some(*first_set_code != set_code);
+                }
+            }
+        }
+    }
+
     /// Add set to dictionary
     pub fn add_set(&mut self, set_code: String, set_data: MtgjsonSetObject) {
         self.all_sets_dict.insert(set_code, set_data);
@@ -225,3 +306,78 @@ impl Default for MtgjsonAllPrintings {
 }
 
 impl JsonObject for MtgjsonAllPrintings {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set_with_card(code: &str, release_date: &str, is_reprint: bool) -> MtgjsonSetObject {
+        let mut mtgjson_set = MtgjsonSetObject::new();
+        mtgjson_set.code = Some(code.to_string());
+        mtgjson_set.release_date = release_date.to_string();
+
+        let mut card = MtgjsonCardObject::new(false);
+        card.name = "Lightning Bolt".to_string();
+        card.set_code = code.to_string();
+        card.identifiers.scryfall_oracle_id = Some("bolt-oracle".to_string());
+        card.is_reprint = Some(is_reprint);
+        mtgjson_set.cards.push(card);
+        mtgjson_set
+    }
+
+    #[test]
+    fn test_add_first_printings_reconciles_is_reprint() {
+        let mut all_printings = MtgjsonAllPrintings::new();
+        // Scryfall data claims the reverse of what release order says
+        all_printings.add_set("M10".to_string(), set_with_card("M10", "2009-07-17", false));
+        all_printings.add_set("LEA".to_string(), set_with_card("LEA", "1993-08-05", true));
+
+        all_printings.add_first_printings();
+
+        let lea_card = &all_printings.all_sets_dict["LEA"].cards[0];
+        assert_eq!(lea_card.first_printing.as_deref(), Some("LEA"));
+        assert_eq!(lea_card.is_reprint, Some(false));
+
+        let m10_card = &all_printings.all_sets_dict["M10"].cards[0];
+        assert_eq!(m10_card.first_printing.as_deref(), Some("LEA"));
+        assert_eq!(m10_card.is_reprint, Some(true));
+    }
+
+    #[test]
+    fn test_add_first_printings_skips_set_exclusive_cards() {
+        let mut all_printings = MtgjsonAllPrintings::new();
+        all_printings.add_set("LEA".to_string(), set_with_card("LEA", "1993-08-05", false));
+        let mut exclusive_set = set_with_card("M10", "2009-07-17", true);
+        exclusive_set.cards[0].name = "Ajani Goldmane".to_string();
+        exclusive_set.cards[0].identifiers.scryfall_oracle_id = Some("ajani-oracle".to_string());
+        all_printings.add_set("M10".to_string(), exclusive_set);
+
+        all_printings.add_first_printings();
+
+        for set_code in ["LEA", "M10"] {
+            let card = &all_printings.all_sets_dict[set_code].cards[0];
+            assert_eq!(card.first_printing, None);
+            assert_eq!(card.is_reprint, Some(false));
+        }
+    }
+
+    #[test]
+    fn test_add_first_printings_breaks_release_date_ties_on_set_code() {
+        let mut all_printings = MtgjsonAllPrintings::new();
+        all_printings.add_set(
+            "PLEA".to_string(),
+            set_with_card("PLEA", "1993-08-05", false),
+        );
+        all_printings.add_set("LEA".to_string(), set_with_card("LEA", "1993-08-05", true));
+
+        all_printings.add_first_printings();
+
+        let lea_card = &all_printings.all_sets_dict["LEA"].cards[0];
+        assert_eq!(lea_card.first_printing.as_deref(), Some("LEA"));
+        assert_eq!(lea_card.is_reprint, Some(false));
+
+        let plea_card = &all_printings.all_sets_dict["PLEA"].cards[0];
+        assert_eq!(plea_card.first_printing.as_deref(), Some("LEA"));
+        assert_eq!(plea_card.is_reprint, Some(true));
+    }
+}
@@ -1,25 +1,159 @@
-use crate::classes::JsonObject;
+use crate::classes::{JsonObject, MtgjsonCardObject, MtgjsonIdentifiers, MtgjsonLegalitiesObject};
+use crate::compiled_classes::MtgjsonAllPrintings;
 use pyo3::prelude::*;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+/// Matches the " (a)" style suffix some face names carry
+static FACE_SUFFIX_REGEX: OnceLock<Regex> = OnceLock::new();
 
 /// MTGJSON AtomicCards Object
+/// Every card collapsed to its oracle-level attributes, keyed by card name.
+/// Multi-faced cards have one entry per face, ordered by side.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[pyclass(name = "MtgjsonAtomicCards")]
 pub struct MtgjsonAtomicCards {
     #[pyo3(get, set)]
-    pub atomic_cards_dict: HashMap<String, Vec<String>>,
+    pub atomic_cards_dict: BTreeMap<String, Vec<MtgjsonCardObject>>,
 }
 
 #[pymethods]
 impl MtgjsonAtomicCards {
     #[new]
     #[pyo3(signature = (cards_data=None))]
-    pub fn new(cards_data: Option<HashMap<String, Vec<String>>>) -> Self {
+    pub fn new(cards_data: Option<BTreeMap<String, Vec<MtgjsonCardObject>>>) -> Self {
         Self {
             atomic_cards_dict: cards_data.unwrap_or_default(),
         }
     }
+
+    /// Convert to JSON string
+    pub fn to_json(&self) -> PyResult<String> {
+        serde_json::to_string(&self.atomic_cards_json()).map_err(|e| {
+            pyo3::exceptions::PyValueError::new_err(format!("Serialization error: {}", e))
+        })
+    }
+}
+
+impl MtgjsonAtomicCards {
+    /// Collapse every printing in AllPrintings into its atomic entries
+    pub fn from_all_printings(all_printings: &MtgjsonAllPrintings) -> Self {
+        let mut atomic_cards = Self::new(None);
+
+        // Walk sets in a stable order so merged fields are deterministic
+        let mut set_codes: Vec<&String> = all_printings.all_sets_dict.keys().collect();
+        set_codes.sort();
+        for set_code in set_codes {
+            atomic_cards.add_cards(&all_printings.all_sets_dict[set_code].cards);
+        }
+
+        atomic_cards
+    }
+
+    /// Add each card's atomic attributes, merging printings of the same card
+    pub fn add_cards(&mut self, cards: &[MtgjsonCardObject]) {
+        for card in cards {
+            self.add_card(card);
+        }
+    }
+
+    fn add_card(&mut self, card: &MtgjsonCardObject) {
+        let mut atomic_card = card.to_atomic();
+
+        for foreign_data in atomic_card.foreign_data.iter_mut() {
+            foreign_data.multiverse_id = None;
+            foreign_data.identifiers = MtgjsonIdentifiers::new();
+        }
+
+        // Strip out the (a), (b) stuff
+        let face_suffix_regex =
+            FACE_SUFFIX_REGEX.get_or_init(|| Regex::new(r"^([^\n]+) \([a-z]\)$").unwrap());
+        let card_name = face_suffix_regex
+            .captures(&card.name)
+            .map(|captures| captures[1].to_string())
+            .unwrap_or_else(|| card.name.clone());
+
+        let entries = self.atomic_cards_dict.entry(card_name).or_default();
+
+        match entries
+            .iter_mut()
+            .find(|entry| entry.text == atomic_card.text)
+        {
+            Some(entry) => {
+                // Some printings might not have foreign data or legalities,
+                // so fill them in from this one
+                if entry.foreign_data.is_empty() {
+                    entry.foreign_data = atomic_card.foreign_data.clone();
+                }
+                if entry.legalities == MtgjsonLegalitiesObject::default() {
+                    entry.legalities = atomic_card.legalities.clone();
+                }
+                // The EDHREC rank belongs to the oracle card, so any printing
+                // that has one supplies it
+                match (entry.edhrec_rank, atomic_card.edhrec_rank) {
+                    (None, Some(rank)) => entry.edhrec_rank = Some(rank),
+                    (Some(held_rank), Some(rank)) if held_rank != rank => eprintln!(
+                        "Warning: {} has EDHREC rank {} in {} but {} elsewhere",
+                        card.name, rank, card.set_code, held_rank
+                    ),
+                    _ => {}
+                }
+            }
+            None => {
+                entries.push(atomic_card.clone());
+                entries.sort_by_key(|entry| entry.side.clone().unwrap_or_else(|| "z".to_string()));
+            }
+        }
+
+        // Foreign data isn't always included by upstreams, so share it
+        // between the entries with the same text
+        let held_foreign_data = if !atomic_card.foreign_data.is_empty() {
+            Some((atomic_card.foreign_data, atomic_card.text))
+        } else {
+            entries
+                .iter()
+                .find(|entry| !entry.foreign_data.is_empty())
+                .map(|entry| (entry.foreign_data.clone(), entry.text.clone()))
+        };
+        if let Some((foreign_data, held_text)) = held_foreign_data {
+            for entry in entries.iter_mut().filter(|entry| entry.text == held_text) {
+                entry.foreign_data = foreign_data.clone();
+            }
+        }
+    }
+
+    /// Every entry as JSON, keeping only the card's atomic keys
+    pub fn atomic_cards_json(&self) -> BTreeMap<String, Vec<Value>> {
+        self.atomic_cards_dict
+            .iter()
+            .map(|(name, entries)| {
+                (
+                    name.clone(),
+                    entries.iter().filter_map(atomic_card_json).collect(),
+                )
+            })
+            .collect()
+    }
+}
+
+/// A card's JSON restricted to its atomic keys
+fn atomic_card_json(card: &MtgjsonCardObject) -> Option<Value> {
+    let Ok(Value::Object(mut serialized_card)) = serde_json::to_value(card) else {
+        return None;
+    };
+
+    let atomic_keys = card.get_atomic_keys();
+    serialized_card.retain(|key, value| {
+        // The card serializes its type line as "type_"
+        let key = if key == "type_" { "type" } else { key.as_str() };
+        let is_empty = value.is_null() || value.as_object().is_some_and(|o| o.is_empty());
+        !is_empty && atomic_keys.iter().any(|atomic_key| atomic_key == key)
+    });
+
+    Some(Value::Object(serialized_card))
 }
 
 impl Default for MtgjsonAtomicCards {
@@ -29,3 +163,117 @@ impl Default for MtgjsonAtomicCards {
 }
 
 impl JsonObject for MtgjsonAtomicCards {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::classes::MtgjsonSetObject;
+
+    fn add_set(all_printings: &mut MtgjsonAllPrintings, code: &str, cards: Vec<MtgjsonCardObject>) {
+        let mut mtgjson_set = MtgjsonSetObject::new();
+        mtgjson_set.code = Some(code.to_string());
+        mtgjson_set.cards = cards;
+        all_printings
+            .all_sets_dict
+            .insert(code.to_string(), mtgjson_set);
+    }
+
+    fn printing(name: &str, set_code: &str, number: &str, is_reprint: bool) -> MtgjsonCardObject {
+        let mut card = MtgjsonCardObject::new(false);
+        card.name = name.to_string();
+        card.set_code = set_code.to_string();
+        card.number = number.to_string();
+        card.is_reprint = Some(is_reprint);
+        card.finishes = vec!["nonfoil".to_string(), "foil".to_string()];
+        card.uuid = format!("{}-{}", set_code, number);
+        card.identifiers.scryfall_id = Some(format!("scryfall-{}-{}", set_code, number));
+        card
+    }
+
+    #[test]
+    fn test_printings_collapse_to_one_atomic_card() {
+        let mut all_printings = MtgjsonAllPrintings::new();
+        for (set_code, number, is_reprint) in [
+            ("TS1", "1", false),
+            ("TS2", "10", true),
+            ("TS3", "100", true),
+        ] {
+            let mut card = printing("Llanowar Elves", set_code, number, is_reprint);
+            card.mana_cost = "{G}".to_string();
+            card.type_ = "Creature — Elf Druid".to_string();
+            card.text = "{T}: Add {G}.".to_string();
+            card.identifiers.scryfall_oracle_id = Some("oracle-elves".to_string());
+            card.legalities.vintage = "Legal".to_string();
+            add_set(&mut all_printings, set_code, vec![card]);
+        }
+        all_printings.add_first_printings();
+
+        let atomic_cards = MtgjsonAtomicCards::from_all_printings(&all_printings);
+
+        let atomic_cards_json = atomic_cards.atomic_cards_json();
+        let entries = &atomic_cards_json["Llanowar Elves"];
+        assert_eq!(entries.len(), 1);
+        let entry = &entries[0];
+        assert_eq!(entry["text"], "{T}: Add {G}.");
+        assert_eq!(entry["mana_cost"], "{G}");
+        assert_eq!(entry["type_"], "Creature — Elf Druid");
+        assert_eq!(entry["legalities"]["vintage"], "Legal");
+        assert_eq!(entry["first_printing"], "TS1");
+        assert_eq!(
+            entry["identifiers"],
+            serde_json::json!({"scryfall_oracle_id": "oracle-elves"})
+        );
+        for printing_key in ["number", "set_code", "finishes", "uuid"] {
+            assert!(
+                entry.get(printing_key).is_none(),
+                "{} was kept",
+                printing_key
+            );
+        }
+    }
+
+    #[test]
+    fn test_atomic_card_keeps_edhrec_rank_from_any_printing() {
+        let mut all_printings = MtgjsonAllPrintings::new();
+        for (set_code, edhrec_rank) in [("TS1", None), ("TS2", Some(42)), ("TS3", None)] {
+            let mut card = printing("Sol Ring", set_code, "1", set_code != "TS1");
+            card.text = "{T}: Add {C}{C}.".to_string();
+            card.edhrec_rank = edhrec_rank;
+            add_set(&mut all_printings, set_code, vec![card]);
+        }
+
+        let atomic_cards = MtgjsonAtomicCards::from_all_printings(&all_printings);
+
+        let entries = &atomic_cards.atomic_cards_dict["Sol Ring"];
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].edhrec_rank, Some(42));
+    }
+
+    #[test]
+    fn test_modal_double_faced_card_has_an_entry_per_face() {
+        let name = "Delver of Secrets // Insectile Aberration";
+        let mut back = printing(name, "TST", "51", false);
+        back.face_name = Some("Insectile Aberration".to_string());
+        back.side = Some("b".to_string());
+        back.text = "Flying".to_string();
+        let mut front = printing(name, "TST", "51", false);
+        front.face_name = Some("Delver of Secrets".to_string());
+        front.side = Some("a".to_string());
+        front.text =
+            "At the beginning of your upkeep, look at the top card of your library.".to_string();
+
+        let mut all_printings = MtgjsonAllPrintings::new();
+        add_set(&mut all_printings, "TST", vec![back, front]);
+
+        let atomic_cards = MtgjsonAtomicCards::from_all_printings(&all_printings);
+
+        assert_eq!(atomic_cards.atomic_cards_dict.len(), 1);
+        let faces = &atomic_cards.atomic_cards_dict[name];
+        assert_eq!(faces.len(), 2);
+        assert_eq!(faces[0].face_name.as_deref(), Some("Delver of Secrets"));
+        assert_eq!(faces[0].side.as_deref(), Some("a"));
+        assert_eq!(faces[1].face_name.as_deref(), Some("Insectile Aberration"));
+        assert_eq!(faces[1].side.as_deref(), Some("b"));
+        assert!(faces.iter().all(|face| face.number.is_empty()));
+    }
+}
@@ -1,14 +1,17 @@
 use crate::classes::JsonObject;
+use crate::compiled_classes::MtgjsonAllPrintings;
 use pyo3::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 /// MTGJSON CardTypes Object
+/// Every card type seen, keyed by lowercase type name, with the subtypes and
+/// supertypes printed alongside it.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[pyclass(name = "MtgjsonCardTypesObject")]
 pub struct MtgjsonCardTypesObject {
     #[pyo3(get, set)]
-    pub types: HashMap<String, HashMap<String, Vec<String>>>,
+    pub types: BTreeMap<String, BTreeMap<String, Vec<String>>>,
 }
 
 #[pymethods]
@@ -16,11 +19,49 @@ impl MtgjsonCardTypesObject {
     #[new]
     pub fn new() -> Self {
         Self {
-            types: HashMap::new(),
+            types: BTreeMap::new(),
         }
     }
 }
 
+impl MtgjsonCardTypesObject {
+    /// Aggregate the subtypes and supertypes of every card and token
+    /// (emblems included) in AllPrintings under each of their types
+    pub fn from_all_printings(all_printings: &MtgjsonAllPrintings) -> Self {
+        let mut seen_types: BTreeMap<String, (BTreeSet<String>, BTreeSet<String>)> =
+            BTreeMap::new();
+
+        let cards = all_printings
+            .all_sets_dict
+            .values()
+            .flat_map(|mtgjson_set| mtgjson_set.cards.iter().chain(&mtgjson_set.tokens));
+        for card in cards {
+            let (super_types, types, sub_types) =
+                crate::builders::set_builder::parse_card_types(&card.type_);
+
+            // "Token" marks the card as a token rather than being a type
+            for card_type in types.iter().filter(|t| t.as_str() != "Token") {
+                let (seen_sub_types, seen_super_types) =
+                    seen_types.entry(card_type.to_lowercase()).or_default();
+                seen_sub_types.extend(sub_types.iter().cloned());
+                seen_super_types.extend(super_types.iter().cloned());
+            }
+        }
+
+        let types = seen_types
+            .into_iter()
+            .map(|(card_type, (sub_types, super_types))| {
+                let mut entry = BTreeMap::new();
+                entry.insert("sub_types".to_string(), sub_types.into_iter().collect());
+                entry.insert("super_types".to_string(), super_types.into_iter().collect());
+                (card_type, entry)
+            })
+            .collect();
+
+        Self { types }
+    }
+}
+
 impl Default for MtgjsonCardTypesObject {
     fn default() -> Self {
         Self::new()
@@ -28,3 +69,43 @@ impl Default for MtgjsonCardTypesObject {
 }
 
 impl JsonObject for MtgjsonCardTypesObject {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::classes::{MtgjsonCardObject, MtgjsonSetObject};
+
+    fn card(type_line: &str, is_token: bool) -> MtgjsonCardObject {
+        let mut card = MtgjsonCardObject::new(is_token);
+        card.type_ = type_line.to_string();
+        card
+    }
+
+    #[test]
+    fn test_from_all_printings_merges_subtypes() {
+        let mut mtgjson_set = MtgjsonSetObject::new();
+        mtgjson_set.code = Some("TST".to_string());
+        mtgjson_set.cards = vec![
+            card("Creature — Elf Warrior", false),
+            card("Legendary Creature — Elf Druid", false),
+            card("Instant", false),
+        ];
+        mtgjson_set.tokens = vec![
+            card("Token Creature — Elf", true),
+            card("Emblem — Jace", true),
+        ];
+        let mut all_printings = MtgjsonAllPrintings::new();
+        all_printings
+            .all_sets_dict
+            .insert("TST".to_string(), mtgjson_set);
+
+        let card_types = MtgjsonCardTypesObject::from_all_printings(&all_printings);
+
+        let creature = &card_types.types["creature"];
+        assert_eq!(creature["sub_types"], vec!["Druid", "Elf", "Warrior"]);
+        assert_eq!(creature["super_types"], vec!["Legendary"]);
+        assert!(card_types.types["instant"]["sub_types"].is_empty());
+        assert_eq!(card_types.types["emblem"]["sub_types"], vec!["Jace"]);
+        assert!(!card_types.types.contains_key("token"));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::classes::{JsonObject, MtgjsonDeckHeaderObject};
+use crate::classes::{JsonObject, MtgjsonDeckHeaderObject, MtgjsonDeckObject, MtgjsonUtils};
 use pyo3::prelude::*;
 use serde::{Deserialize, Serialize};
 
@@ -20,6 +20,13 @@ impl MtgjsonDeckObjectList {
         }
     }
 
+    /// Build the deck index from built decks across all sets, sorted by
+    /// release date then name
+    #[staticmethod]
+    pub fn from_decks(decks: Vec<MtgjsonDeckObject>) -> Self {
+        Self::from_deck_refs(&decks)
+    }
+
     /// Create empty deck list
     #[staticmethod]
     pub fn empty() -> Self {
@@ -98,6 +105,17 @@ impl MtgjsonDeckObjectList {
             .sort_by(|a, b| a.release_date.cmp(&b.release_date));
     }
 
+    /// Sort decks by release date then name, with undated decks last
+    pub fn sort_by_release_date_then_name(&mut self) {
+        self.decks.sort_by(|a, b| {
+            (a.release_date.is_empty(), &a.release_date, &a.name).cmp(&(
+                b.release_date.is_empty(),
+                &b.release_date,
+                &b.name,
+            ))
+        });
+    }
+
     /// Sort decks by code
     pub fn sort_by_code(&mut self) {
         self.decks.sort_by(|a, b| a.code.cmp(&b.code));
@@ -129,6 +147,27 @@ impl MtgjsonDeckObjectList {
     }
 }
 
+impl MtgjsonDeckObjectList {
+    /// `from_decks` over borrowed decks
+    pub fn from_deck_refs<'a>(decks: impl IntoIterator<Item = &'a MtgjsonDeckObject>) -> Self {
+        let deck_headers = decks
+            .into_iter()
+            .map(|deck| {
+                let mut deck_header = MtgjsonDeckHeaderObject::new(deck);
+                if deck_header.file_name.is_empty() {
+                    deck_header.file_name =
+                        MtgjsonUtils::sanitize_deck_name(&deck.name, &deck.code);
+                }
+                deck_header
+            })
+            .collect();
+
+        let mut deck_list = Self::new(deck_headers);
+        deck_list.sort_by_release_date_then_name();
+        deck_list
+    }
+}
+
 impl Default for MtgjsonDeckObjectList {
     fn default() -> Self {
         Self::empty()
@@ -199,6 +238,47 @@ mod tests {
         assert_eq!(standard_decks.len(), 1);
     }
 
+    #[test]
+    fn test_from_decks_sorted_by_release_date_then_name() {
+        let deck = |name: &str, code: &str, deck_type: &str, release_date: &str| {
+            let mut deck = MtgjsonDeckObject::new(name, None);
+            deck.code = code.to_string();
+            deck.type_ = deck_type.to_string();
+            deck.release_date = release_date.to_string();
+            deck
+        };
+        let mut named_file = deck("Zombie Horde", "M21", "Intro Pack", "2020-07-03");
+        named_file.file_name = "ZombieHorde_M21".to_string();
+
+        let decks = vec![
+            deck("Undated Deck", "PRM", "Box Set", ""),
+            named_file,
+            deck("Arcane Maelstrom", "C20", "Commander Deck", "2020-04-17"),
+            deck("Angel Army", "M21", "Intro Pack", "2020-07-03"),
+        ];
+
+        let deck_list = MtgjsonDeckObjectList::from_decks(decks.clone());
+        let names: Vec<&str> = deck_list.decks.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "Arcane Maelstrom",
+                "Angel Army",
+                "Zombie Horde",
+                "Undated Deck"
+            ]
+        );
+
+        for header in &deck_list.decks {
+            let source = decks.iter().find(|d| d.name == header.name).unwrap();
+            assert_eq!(header.code, source.code);
+            assert_eq!(header.release_date, source.release_date);
+            assert_eq!(header.type_, source.type_);
+        }
+        assert_eq!(deck_list.decks[0].file_name, "ARCANEMAELSTROM_C20");
+        assert_eq!(deck_list.decks[2].file_name, "ZombieHorde_M21");
+    }
+
     #[test]
     fn test_unique_types() {
         let deck_headers = vec![
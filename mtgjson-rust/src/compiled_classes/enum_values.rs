@@ -1,14 +1,45 @@
+use crate::classes::base::to_camel_case;
 use crate::classes::JsonObject;
 use pyo3::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Card fields whose observed values are enumerated
+const CARD_ENUM_KEYS: &[&str] = &[
+    "availability",
+    "booster_types",
+    "border_color",
+    "color_identity",
+    "color_indicator",
+    "colors",
+    "duel_deck",
+    "finishes",
+    "frame_effects",
+    "frame_version",
+    "language",
+    "layout",
+    "promo_types",
+    "rarity",
+    "security_stamp",
+    "side",
+    "subtypes",
+    "supertypes",
+    "types",
+    "watermark",
+];
+const FOREIGN_DATA_ENUM_KEYS: &[&str] = &["language"];
+const SET_ENUM_KEYS: &[&str] = &["type_", "languages"];
+const SEALED_PRODUCT_ENUM_KEYS: &[&str] = &["category", "subtype"];
+const DECK_ENUM_KEYS: &[&str] = &["type_"];
 
 /// MTGJSON EnumValues Object
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[pyclass(name = "MtgjsonEnumValues")]
 pub struct MtgjsonEnumValues {
+    /// Object type -> field -> every value observed for that field
     #[pyo3(get, set)]
-    pub attr_value_dict: HashMap<String, String>,
+    pub attr_value_dict: BTreeMap<String, BTreeMap<String, BTreeSet<String>>>,
 }
 
 #[pymethods]
@@ -16,11 +47,96 @@ impl MtgjsonEnumValues {
     #[new]
     pub fn new() -> Self {
         Self {
-            attr_value_dict: HashMap::new(),
+            attr_value_dict: BTreeMap::new(),
         }
     }
 }
 
+impl MtgjsonEnumValues {
+    /// Record the values of `keys` observed on a serialized object
+    fn record(&mut self, object_type: &str, object: &Value, keys: &[&str]) {
+        let observed_fields = self
+            .attr_value_dict
+            .entry(object_type.to_string())
+            .or_default();
+
+        for key in keys {
+            let observed = observed_fields.entry(to_camel_case(key)).or_default();
+            match object.get(key) {
+                Some(Value::String(value)) if !value.is_empty() => {
+                    observed.insert(value.clone());
+                }
+                Some(Value::Array(values)) => {
+                    observed.extend(values.iter().filter_map(|v| v.as_str()).map(String::from));
+                }
+                // Flag objects such as availability list the flags that are set
+                Some(Value::Object(flags)) => {
+                    observed.extend(
+                        flags
+                            .iter()
+                            .filter(|(_, set)| set.as_bool() == Some(true))
+                            .map(|(flag, _)| flag.clone()),
+                    );
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Record every enumerated value observed in a serialized set
+    pub fn add_set(&mut self, mtgjson_set: &Value) {
+        let entries = |key: &str| {
+            mtgjson_set
+                .get(key)
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+        };
+
+        self.record("set", mtgjson_set, SET_ENUM_KEYS);
+
+        for card in entries("cards").chain(entries("tokens")) {
+            self.record("card", card, CARD_ENUM_KEYS);
+            for foreign_data in card
+                .get("foreign_data")
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+            {
+                self.record("foreignData", foreign_data, FOREIGN_DATA_ENUM_KEYS);
+            }
+        }
+
+        for sealed_product in entries("sealed_product") {
+            self.record("sealedProduct", sealed_product, SEALED_PRODUCT_ENUM_KEYS);
+        }
+
+        for deck in entries("decks") {
+            self.record("deck", deck, DECK_ENUM_KEYS);
+        }
+    }
+
+    /// Warning naming the observed card frame effects missing from
+    /// `known_frame_effects`. `None` when every effect is known.
+    pub fn unknown_frame_effects_warning(&self, known_frame_effects: &[String]) -> Option<String> {
+        let unknown_frame_effects: Vec<&str> = self
+            .attr_value_dict
+            .get("card")
+            .and_then(|fields| fields.get("frameEffects"))
+            .into_iter()
+            .flatten()
+            .filter(|frame_effect| !known_frame_effects.contains(frame_effect))
+            .map(String::as_str)
+            .collect();
+        (!unknown_frame_effects.is_empty()).then(|| {
+            format!(
+                "Warning: EnumValues has unrecognized frame effects: {}",
+                unknown_frame_effects.join(", ")
+            )
+        })
+    }
+}
+
 impl Default for MtgjsonEnumValues {
     fn default() -> Self {
         Self::new()
@@ -28,3 +144,88 @@ impl Default for MtgjsonEnumValues {
 }
 
 impl JsonObject for MtgjsonEnumValues {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::classes::{MtgjsonCardObject, MtgjsonSetObject};
+
+    fn card(rarity: &str, finishes: &[&str], frame_effects: &[&str]) -> MtgjsonCardObject {
+        let mut card = MtgjsonCardObject::new(false);
+        card.rarity = rarity.to_string();
+        card.border_color = "black".to_string();
+        card.layout = "normal".to_string();
+        card.finishes = finishes.iter().map(|f| f.to_string()).collect();
+        card.frame_effects = frame_effects.iter().map(|f| f.to_string()).collect();
+        card
+    }
+
+    fn values(enum_values: &MtgjsonEnumValues, object_type: &str, field: &str) -> Vec<String> {
+        enum_values.attr_value_dict[object_type][field]
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    #[test]
+    fn test_add_set_enumerates_card_and_set_fields() {
+        let mut mtgjson_set = MtgjsonSetObject::new();
+        mtgjson_set.code = Some("TST".to_string());
+        mtgjson_set.type_ = "expansion".to_string();
+        mtgjson_set.cards = vec![
+            card("rare", &["nonfoil", "foil"], &["legendary"]),
+            card("common", &["nonfoil"], &[]),
+            card("rare", &["etched"], &["showcase", "legendary"]),
+        ];
+        let mut promo_set = MtgjsonSetObject::new();
+        promo_set.code = Some("PTS".to_string());
+        promo_set.type_ = "promo".to_string();
+
+        let mut enum_values = MtgjsonEnumValues::new();
+        for set in [mtgjson_set, promo_set] {
+            enum_values.add_set(&serde_json::to_value(&set).unwrap());
+        }
+
+        assert_eq!(values(&enum_values, "card", "rarity"), ["common", "rare"]);
+        assert_eq!(
+            values(&enum_values, "card", "finishes"),
+            ["etched", "foil", "nonfoil"]
+        );
+        assert_eq!(
+            values(&enum_values, "card", "frameEffects"),
+            ["legendary", "showcase"]
+        );
+        assert_eq!(values(&enum_values, "card", "borderColor"), ["black"]);
+        assert_eq!(values(&enum_values, "card", "layout"), ["normal"]);
+        assert_eq!(values(&enum_values, "set", "type"), ["expansion", "promo"]);
+    }
+
+    #[test]
+    fn test_unknown_frame_effects_are_warned() {
+        let mut mtgjson_set = MtgjsonSetObject::new();
+        mtgjson_set.cards = vec![
+            card("rare", &["nonfoil"], &["legendary", "glowinthedark"]),
+            card("rare", &["foil"], &["showcase"]),
+        ];
+        let mut enum_values = MtgjsonEnumValues::new();
+        enum_values.add_set(&serde_json::to_value(&mtgjson_set).unwrap());
+
+        let known_frame_effects = vec!["legendary".to_string(), "showcase".to_string()];
+        assert_eq!(
+            enum_values
+                .unknown_frame_effects_warning(&known_frame_effects)
+                .as_deref(),
+            Some("Warning: EnumValues has unrecognized frame effects: glowinthedark")
+        );
+
+        let known_frame_effects = vec![
+            "glowinthedark".to_string(),
+            "legendary".to_string(),
+            "showcase".to_string(),
+        ];
+        assert_eq!(
+            enum_values.unknown_frame_effects_warning(&known_frame_effects),
+            None
+        );
+    }
+}
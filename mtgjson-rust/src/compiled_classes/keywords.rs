@@ -1,6 +1,8 @@
 use crate::classes::JsonObject;
+use crate::compiled_classes::MtgjsonAllPrintings;
 use pyo3::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
 
 /// MTGJSON Keywords Object
 /// Rust equivalent of MtgjsonKeywordsObject
@@ -260,6 +262,39 @@ impl MtgjsonKeywords {
     }
 }
 
+impl MtgjsonKeywords {
+    /// Collect the distinct keywords seen on cards across AllPrintings,
+    /// sorted and categorized using the reference lists. Keywords missing
+    /// from every list are counted as keyword abilities, the largest group.
+    pub fn from_all_printings(all_printings: &MtgjsonAllPrintings) -> Self {
+        let reference = Self::new();
+        let mut ability_words = BTreeSet::new();
+        let mut keyword_actions = BTreeSet::new();
+        let mut keyword_abilities = BTreeSet::new();
+
+        let keywords = all_printings
+            .all_sets_dict
+            .values()
+            .flat_map(|mtgjson_set| mtgjson_set.cards.iter().chain(&mtgjson_set.tokens))
+            .flat_map(|card| card.keywords.iter());
+        for keyword in keywords {
+            if reference.is_ability_word(keyword) {
+                ability_words.insert(keyword.clone());
+            } else if reference.is_keyword_action(keyword) {
+                keyword_actions.insert(keyword.clone());
+            } else {
+                keyword_abilities.insert(keyword.clone());
+            }
+        }
+
+        Self::from_lists(
+            ability_words.into_iter().collect(),
+            keyword_actions.into_iter().collect(),
+            keyword_abilities.into_iter().collect(),
+        )
+    }
+}
+
 impl Default for MtgjsonKeywords {
     fn default() -> Self {
         Self::new()
@@ -305,6 +340,39 @@ mod tests {
         assert!(keywords.is_ability_word("Landfall"));
     }
 
+    #[test]
+    fn test_from_all_printings_categorizes_card_keywords() {
+        use crate::classes::{MtgjsonCardObject, MtgjsonSetObject};
+
+        let card = |keywords: &[&str]| {
+            let mut card = MtgjsonCardObject::new(false);
+            card.keywords = keywords.iter().map(|k| k.to_string()).collect();
+            card
+        };
+        let mut mtgjson_set = MtgjsonSetObject::new();
+        mtgjson_set.code = Some("TST".to_string());
+        mtgjson_set.cards = vec![
+            card(&["Flying", "Vigilance"]),
+            card(&["Landfall"]),
+            card(&["Scry", "Flying"]),
+            card(&[]),
+        ];
+        mtgjson_set.tokens = vec![card(&["Haste"])];
+        let mut all_printings = MtgjsonAllPrintings::new();
+        all_printings
+            .all_sets_dict
+            .insert("TST".to_string(), mtgjson_set);
+
+        let keywords = MtgjsonKeywords::from_all_printings(&all_printings);
+
+        assert_eq!(keywords.ability_words, vec!["Landfall"]);
+        assert_eq!(keywords.keyword_actions, vec!["Scry"]);
+        assert_eq!(
+            keywords.keyword_abilities,
+            vec!["Flying", "Haste", "Vigilance"]
+        );
+    }
+
     #[test]
     fn test_from_lists() {
         let abilities = vec!["Test Ability".to_string()];
@@ -1,14 +1,65 @@
-use crate::classes::JsonObject;
+use crate::base::skip_if_empty_optional_string;
+use crate::classes::{JsonObject, MtgjsonSetObject};
 use pyo3::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+
+/// One set's summary in SetList
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+#[pyclass(name = "MtgjsonSetListEntry")]
+pub struct MtgjsonSetListEntry {
+    #[pyo3(get, set)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_set_size: Option<i32>,
+
+    #[pyo3(get, set)]
+    #[serde(skip_serializing_if = "skip_if_empty_optional_string")]
+    pub code: Option<String>,
+
+    #[pyo3(get, set)]
+    pub is_online_only: bool,
+
+    #[pyo3(get, set)]
+    #[serde(skip_serializing_if = "skip_if_empty_optional_string")]
+    pub keyrune_code: Option<String>,
+
+    #[pyo3(get, set)]
+    pub name: String,
+
+    #[pyo3(get, set)]
+    pub release_date: String,
+
+    #[pyo3(get, set)]
+    pub total_set_size: i32,
+
+    #[pyo3(get, set)]
+    #[serde(rename = "type")]
+    pub type_: String,
+}
+
+impl MtgjsonSetListEntry {
+    /// Summarize a set, leaving out its cards and products
+    pub fn from_set(mtgjson_set: &MtgjsonSetObject) -> Self {
+        Self {
+            base_set_size: mtgjson_set.base_set_size,
+            code: mtgjson_set.code.clone(),
+            is_online_only: mtgjson_set.is_online_only,
+            keyrune_code: mtgjson_set.keyrune_code.clone(),
+            name: mtgjson_set.name.clone(),
+            release_date: mtgjson_set.release_date.clone(),
+            total_set_size: mtgjson_set.total_set_size,
+            type_: mtgjson_set.type_.clone(),
+        }
+    }
+}
 
 /// MTGJSON SetList Object
+/// A lightweight summary of every set, without its cards or products.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[pyclass(name = "MtgjsonSetObjectList")]
 pub struct MtgjsonSetObjectList {
     #[pyo3(get, set)]
-    pub set_list: Vec<HashMap<String, String>>,
+    pub set_list: Vec<MtgjsonSetListEntry>,
 }
 
 #[pymethods]
@@ -19,6 +70,35 @@ impl MtgjsonSetObjectList {
             set_list: Vec::new(),
         }
     }
+
+    /// Convert to JSON string
+    pub fn to_json(&self) -> PyResult<String> {
+        serde_json::to_string(&self.set_list).map_err(|e| {
+            pyo3::exceptions::PyValueError::new_err(format!("Serialization error: {}", e))
+        })
+    }
+}
+
+impl MtgjsonSetObjectList {
+    /// Summarize each set, ordered by release date then code. Sets without
+    /// a release date come last.
+    pub fn from_sets<'a>(sets: impl IntoIterator<Item = &'a MtgjsonSetObject>) -> Self {
+        let mut ordered_sets: Vec<&MtgjsonSetObject> = sets.into_iter().collect();
+        ordered_sets.sort_by(|a, b| {
+            (a.release_date.is_empty(), &a.release_date, &a.code).cmp(&(
+                b.release_date.is_empty(),
+                &b.release_date,
+                &b.code,
+            ))
+        });
+
+        let set_list = ordered_sets
+            .into_iter()
+            .map(MtgjsonSetListEntry::from_set)
+            .collect();
+
+        Self { set_list }
+    }
 }
 
 impl Default for MtgjsonSetObjectList {
@@ -28,3 +108,51 @@ impl Default for MtgjsonSetObjectList {
 }
 
 impl JsonObject for MtgjsonSetObjectList {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::classes::MtgjsonCardObject;
+
+    fn set(code: &str, release_date: &str) -> MtgjsonSetObject {
+        let mut mtgjson_set = MtgjsonSetObject::new();
+        mtgjson_set.code = Some(code.to_string());
+        mtgjson_set.name = format!("Test Set {}", code);
+        mtgjson_set.release_date = release_date.to_string();
+        mtgjson_set.type_ = "expansion".to_string();
+        mtgjson_set.cards.push(MtgjsonCardObject::new(false));
+        mtgjson_set
+    }
+
+    #[test]
+    fn test_from_sets_orders_by_release_date_then_code() {
+        let sets = vec![
+            set("TS3", "2021-06-01"),
+            set("NOD", ""),
+            set("TS2", "2020-01-01"),
+            set("TS1", "2021-06-01"),
+        ];
+
+        let set_list = MtgjsonSetObjectList::from_sets(&sets);
+
+        let codes: Vec<&str> = set_list
+            .set_list
+            .iter()
+            .map(|entry| entry.code.as_deref().unwrap())
+            .collect();
+        assert_eq!(codes, ["TS2", "TS1", "TS3", "NOD"]);
+
+        let summary = serde_json::to_value(&set_list.set_list[0]).unwrap();
+        assert_eq!(summary["name"], "Test Set TS2");
+        assert_eq!(summary["releaseDate"], "2020-01-01");
+        assert_eq!(summary["type"], "expansion");
+        assert_eq!(summary["isOnlineOnly"], false);
+        for excluded_key in ["cards", "tokens", "sealed_product", "decks"] {
+            assert!(
+                summary.get(excluded_key).is_none(),
+                "{} was kept",
+                excluded_key
+            );
+        }
+    }
+}
@@ -13,6 +13,8 @@ pub struct MtgjsonStructures {
     #[pyo3(get)]
     pub atomic_cards: String,
     #[pyo3(get)]
+    pub all_tokens: String,
+    #[pyo3(get)]
     pub all_prices: String,
     #[pyo3(get)]
     pub all_prices_today: String,
@@ -83,6 +85,7 @@ impl MtgjsonStructures {
         Self {
             all_printings: "AllPrintings".to_string(),
             atomic_cards: "AtomicCards".to_string(),
+            all_tokens: "AllTokens".to_string(),
             all_prices: "AllPrices".to_string(),
             all_prices_today: "AllPricesToday".to_string(),
             all_csvs_directory: "AllPrintingsCSVFiles".to_string(),
@@ -118,6 +121,7 @@ impl MtgjsonStructures {
         vec![
             self.all_printings.clone(),
             self.atomic_cards.clone(),
+            self.all_tokens.clone(),
             self.all_prices.clone(),
             self.all_prices_today.clone(),
             self.all_csvs_directory.clone(),
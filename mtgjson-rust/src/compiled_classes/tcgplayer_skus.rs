@@ -1,14 +1,36 @@
 use crate::classes::JsonObject;
 use pyo3::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
+
+/// One TCGPlayer SKU of a card's product
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+#[pyclass(name = "MtgjsonTcgplayerSku")]
+pub struct MtgjsonTcgplayerSku {
+    #[pyo3(get, set)]
+    pub sku_id: u64,
+
+    #[pyo3(get, set)]
+    pub product_id: u64,
+
+    #[pyo3(get, set)]
+    pub condition: String,
+
+    #[pyo3(get, set)]
+    pub language: String,
+
+    #[pyo3(get, set)]
+    pub printing: String,
+}
 
 /// MTGJSON TcgplayerSkus Object
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[pyclass(name = "MtgjsonTcgplayerSkus")]
 pub struct MtgjsonTcgplayerSkus {
+    /// MTGJSON UUID -> every TCGPlayer SKU of the card's product
     #[pyo3(get, set)]
-    pub tcgplayer_skus: HashMap<String, Vec<HashMap<String, String>>>,
+    pub tcgplayer_skus: BTreeMap<String, Vec<MtgjsonTcgplayerSku>>,
 }
 
 #[pymethods]
@@ -17,9 +39,26 @@ impl MtgjsonTcgplayerSkus {
     #[pyo3(signature = (_all_printings_path=None))]
     pub fn new(_all_printings_path: Option<std::path::PathBuf>) -> Self {
         Self {
-            tcgplayer_skus: HashMap::new(),
+            tcgplayer_skus: BTreeMap::new(),
         }
     }
+
+    /// Convert to JSON string
+    pub fn to_json(&self) -> PyResult<String> {
+        serde_json::to_string(&self.tcgplayer_skus).map_err(|e| {
+            pyo3::exceptions::PyValueError::new_err(format!("Serialization error: {}", e))
+        })
+    }
+}
+
+impl MtgjsonTcgplayerSkus {
+    /// Add SKU records under a card's UUID
+    pub fn add_skus(&mut self, uuid: &str, skus: impl IntoIterator<Item = MtgjsonTcgplayerSku>) {
+        self.tcgplayer_skus
+            .entry(uuid.to_string())
+            .or_default()
+            .extend(skus);
+    }
 }
 
 impl Default for MtgjsonTcgplayerSkus {
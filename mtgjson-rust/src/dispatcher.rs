@@ -0,0 +1,1067 @@
+// MTGJSON main executor - Rust port of `mtgjson5.__main__`
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::{BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Once;
+use std::time::{Duration, Instant};
+
+use crate::arg_parser::MtgjsonArgs;
+use crate::builders::compressor::compress_output;
+use crate::builders::output_generator::{OutputGenerator, DEFAULT_INDENT};
+use crate::builders::price_builder::{
+    build_prices_from_archive, build_today_prices_from_providers, inject_card_prices,
+    prune_price_data, update_price_archive_file, DEFAULT_PRICE_RETENTION_DAYS,
+};
+use crate::builders::set_builder::{
+    build_mtgjson_set, check_uuid_collisions, get_additional_set_codes, load_additional_cards,
+    load_all_resources, merge_additional_cards, remap_set_code, set_skipped_enrichment,
+    sort_cards_by, CardSortOrder,
+};
+use crate::classes::utils::MtgjsonUtils;
+use crate::classes::MtgjsonSetObject;
+use crate::notifications::{BuildEvent, BuildNotifications};
+use crate::providers::scryfall::sf_utils::{set_providers_config_path, MtgjsonConfig};
+use crate::providers::{
+    dry_run_s3_upload, set_response_cache, set_response_dump_dir, set_retry_config, upload_to_s3,
+    ResponseCache, RetryConfig, ScryfallProvider, DEFAULT_CACHE_TTL_SECS,
+};
+
+/// Name of the reproducibility manifest written by `--write-manifest`
+pub const BUILD_MANIFEST_FILE_NAME: &str = "build_manifest.json";
+
+/// What was written out for a single set
+#[derive(Debug, Clone, Serialize)]
+pub struct BuiltSetSummary {
+    pub code: String,
+    pub file_name: String,
+    pub card_count: usize,
+    pub token_count: usize,
+    pub deck_count: usize,
+    pub sealed_product_count: usize,
+}
+
+/// Totals printed at the end of a build
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct BuildStats {
+    pub sets_built: usize,
+    pub total_cards: usize,
+    pub total_tokens: usize,
+    pub total_decks: usize,
+    pub total_sealed_products: usize,
+    pub skipped_sets: Vec<String>,
+    pub failed_sets: Vec<String>,
+    pub duration_secs: f64,
+}
+
+impl BuildStats {
+    /// Count a set that was written out
+    pub fn add_set(&mut self, summary: &BuiltSetSummary) {
+        self.sets_built += 1;
+        self.total_cards += summary.card_count;
+        self.total_tokens += summary.token_count;
+        self.total_decks += summary.deck_count;
+        self.total_sealed_products += summary.sealed_product_count;
+    }
+
+    /// Human readable summary of the build
+    pub fn summary(&self) -> String {
+        let list_or_none = |sets: &[String]| {
+            if sets.is_empty() {
+                "none".to_string()
+            } else {
+                sets.join(", ")
+            }
+        };
+
+        format!(
+            "Build summary:\n  Sets built: {}\n  Cards: {}\n  Tokens: {}\n  Decks: {}\n  \
+             Sealed products: {}\n  Duration: {:.1}s\n  Skipped sets: {}\n  Failed sets: {}",
+            self.sets_built,
+            self.total_cards,
+            self.total_tokens,
+            self.total_decks,
+            self.total_sealed_products,
+            self.duration_secs,
+            list_or_none(&self.skipped_sets),
+            list_or_none(&self.failed_sets),
+        )
+    }
+}
+
+/// A set entry in the build manifest
+#[derive(Debug, Clone, Serialize)]
+pub struct BuildManifestSet {
+    #[serde(flatten)]
+    pub summary: BuiltSetSummary,
+    pub sha256: String,
+}
+
+/// Build manifest for reproducibility audits
+#[derive(Debug, Clone, Serialize)]
+pub struct BuildManifest {
+    pub build_date: String,
+    pub version: String,
+    pub arguments: MtgjsonArgs,
+    pub sets: Vec<BuildManifestSet>,
+}
+
+/// Build each set and write it out, returning a summary of each set written.
+/// With `emit_both`, each set is also written pretty as `<CODE>.pretty.json`
+/// alongside the minified `<CODE>.json`, regardless of the pretty setting.
+/// With `strict_uuid`, a set with colliding UUIDs aborts the build.
+/// With `card_prices` (latest prices keyed by UUID), each card carries its
+/// prices in the written file.
+/// Sets in `additional_cards_files` have that file's cards merged in.
+/// With `resume`, sets already written today are skipped.
+/// Each set's cards are written in `card_sort_order`.
+/// Every set built or failed is also counted in `stats`.
+/// Sets come from `build_set`, normally `build_mtgjson_set`.
+pub fn build_and_write_mtgjson_sets(
+    sets_to_build: &[String],
+    output_generator: &OutputGenerator,
+    set_code_remap: &HashMap<String, String>,
+    additional_cards_files: &HashMap<String, String>,
+    emit_both: bool,
+    strict_uuid: bool,
+    resume: bool,
+    card_sort_order: CardSortOrder,
+    card_prices: Option<&Value>,
+    stats: &mut BuildStats,
+    build_set: impl Fn(&str) -> Option<MtgjsonSetObject>,
+) -> PyResult<Vec<BuiltSetSummary>> {
+    println!(
+        "Building {} Sets: {}",
+        sets_to_build.len(),
+        sets_to_build.join(", ")
+    );
+
+    let mut built_sets = Vec::with_capacity(sets_to_build.len());
+    for set_to_build in sets_to_build {
+        let output_code = set_code_remap
+            .get(&set_to_build.to_uppercase())
+            .unwrap_or(set_to_build);
+        let output_dir = Path::new(&output_generator.output_path);
+        if !should_rebuild_set(output_dir, output_code, resume) {
+            println!("Skipping {}, already built today", set_to_build);
+            stats.skipped_sets.push(set_to_build.clone());
+            continue;
+        }
+
+        // Build the full set
+        let mut mtgjson_set = match build_set(set_to_build) {
+            Some(mtgjson_set) => mtgjson_set,
+            None => {
+                eprintln!("Warning: Unable to build {}, skipping it", set_to_build);
+                stats.failed_sets.push(set_to_build.clone());
+                continue;
+            }
+        };
+
+        if let Some(path) = additional_cards_files.get(&set_to_build.to_uppercase()) {
+            let additional_cards = load_additional_cards(Path::new(path))?;
+            println!(
+                "Merging {} additional cards into {}",
+                additional_cards.len(),
+                set_to_build
+            );
+            merge_additional_cards(&mut mtgjson_set, &additional_cards);
+        }
+
+        if let Some(new_code) = set_code_remap.get(&set_to_build.to_uppercase()) {
+            println!("Remapping {} -> {}", set_to_build, new_code);
+            remap_set_code(&mut mtgjson_set, new_code);
+        }
+
+        if strict_uuid {
+            check_uuid_collisions(&mtgjson_set)?;
+        }
+
+        sort_cards_by(&mut mtgjson_set.cards, card_sort_order);
+
+        // Dump set out to file
+        let file_name = mtgjson_set.get_windows_safe_set_code();
+        match card_prices {
+            Some(card_prices) => {
+                let mut set_data = serde_json::to_value(&mtgjson_set).map_err(|e| {
+                    PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                        "Failed to serialize {}: {}",
+                        file_name, e
+                    ))
+                })?;
+                inject_card_prices(&mut set_data, card_prices);
+                write_set_file(output_generator, &file_name, &set_data, emit_both)?;
+            }
+            None => write_set_file(output_generator, &file_name, &mtgjson_set, emit_both)?,
+        }
+
+        let summary = BuiltSetSummary {
+            code: mtgjson_set.code.clone().unwrap_or_default(),
+            file_name: format!("{}.json", file_name),
+            card_count: mtgjson_set.cards.len(),
+            token_count: mtgjson_set.tokens.len(),
+            deck_count: mtgjson_set.decks.len(),
+            sealed_product_count: mtgjson_set.sealed_product.len(),
+        };
+        stats.add_set(&summary);
+        built_sets.push(summary);
+    }
+
+    Ok(built_sets)
+}
+
+/// Just enough of a written set file to tell when it was built
+#[derive(Deserialize)]
+struct WrittenSetMeta {
+    meta: WrittenSetDate,
+}
+
+#[derive(Deserialize)]
+struct WrittenSetDate {
+    date: String,
+}
+
+/// Whether `set_code` needs building. When resuming, a set whose output file
+/// in `output_dir` was written today is kept; a missing, stale or unreadable
+/// file is rebuilt.
+pub fn should_rebuild_set(output_dir: &Path, set_code: &str, resume: bool) -> bool {
+    if !resume {
+        return true;
+    }
+
+    let file_name = format!(
+        "{}.json",
+        MtgjsonUtils::make_windows_safe_filename(set_code)
+    );
+    let set_path = output_dir.join(file_name);
+    let Ok(file) = fs::File::open(&set_path) else {
+        return true;
+    };
+
+    match serde_json::from_reader::<_, WrittenSetMeta>(BufReader::new(file)) {
+        Ok(written) => written.meta.date != chrono::Utc::now().format("%Y-%m-%d").to_string(),
+        Err(e) => {
+            eprintln!(
+                "Warning: Unable to read {:?}, rebuilding it: {}",
+                set_path, e
+            );
+            true
+        }
+    }
+}
+
+/// Write a built set, both minified and pretty with `emit_both`
+fn write_set_file<T: Serialize>(
+    output_generator: &OutputGenerator,
+    file_name: &str,
+    set_data: &T,
+    emit_both: bool,
+) -> PyResult<()> {
+    if emit_both {
+        output_generator.write_object_to_files_minified_and_pretty(file_name, set_data)
+    } else {
+        output_generator.write_object_to_file(file_name, set_data, output_generator.pretty_print)
+    }
+}
+
+/// Build each set one-by-one and output them to a file.
+/// Sets listed in `set_code_remap` are downloaded under their Scryfall code
+/// but written out (code and file name) under the remapped code.
+/// Sets listed in `additional_cards_files` have the cards in that file built
+/// and merged in.
+/// With `resume_build`, sets already written today are skipped.
+/// `sort_cards_by` (number, name or mv) orders each set's cards.
+#[pyfunction]
+#[pyo3(signature = (
+    sets_to_build,
+    output_pretty,
+    set_code_remap=None,
+    output_path=None,
+    emit_both=false,
+    strict_uuid=false,
+    additional_cards_files=None,
+    resume_build=false,
+    sort_cards_by=None
+))]
+pub fn build_mtgjson_sets(
+    sets_to_build: Vec<String>,
+    output_pretty: bool,
+    set_code_remap: Option<HashMap<String, String>>,
+    output_path: Option<String>,
+    emit_both: bool,
+    strict_uuid: bool,
+    additional_cards_files: Option<HashMap<String, String>>,
+    resume_build: bool,
+    sort_cards_by: Option<String>,
+) -> PyResult<()> {
+    let card_sort_order = MtgjsonArgs {
+        sort_cards_by,
+        ..Default::default()
+    }
+    .get_card_sort_order()?;
+    let output_generator = OutputGenerator::new(output_path, Some(output_pretty));
+    build_and_write_mtgjson_sets(
+        &sets_to_build,
+        &output_generator,
+        &set_code_remap.unwrap_or_default(),
+        &additional_cards_files.unwrap_or_default(),
+        emit_both,
+        strict_uuid,
+        resume_build,
+        card_sort_order,
+        None,
+        &mut BuildStats::default(),
+        build_mtgjson_set,
+    )?;
+    Ok(())
+}
+
+/// Drop every set that sorts before `resume_from_set`, returning the rest sorted
+fn apply_resume_from_set(
+    mut sets_to_build: Vec<String>,
+    resume_from_set: Option<&str>,
+) -> Vec<String> {
+    sets_to_build.sort();
+    if let Some(resume_from_set) = resume_from_set {
+        let resume_from_set = resume_from_set.to_uppercase();
+        sets_to_build.retain(|set_code| set_code.to_uppercase() >= resume_from_set);
+    }
+    sets_to_build
+}
+
+/// Add the locally-defined `additional_sets` Scryfall doesn't list to
+/// `sets_to_build`, leaving out any in `skip_sets`
+fn add_additional_sets(
+    sets_to_build: Vec<String>,
+    additional_sets: &HashSet<String>,
+    skip_sets: &[String],
+) -> Vec<String> {
+    let skip_sets: HashSet<String> = skip_sets.iter().map(|s| s.to_uppercase()).collect();
+    let mut sets: HashSet<String> = sets_to_build.iter().map(|s| s.to_uppercase()).collect();
+    sets.extend(
+        additional_sets
+            .iter()
+            .map(|s| s.to_uppercase())
+            .filter(|s| !skip_sets.contains(s)),
+    );
+    sets.into_iter().collect()
+}
+
+/// Determine which sets the arguments ask for
+fn get_sets_to_build(args: &MtgjsonArgs) -> PyResult<Vec<String>> {
+    let sets_to_build = if args.all_sets || args.resume_build {
+        let provider = ScryfallProvider::new()?;
+        let sets_to_build = provider.get_sets_to_build(
+            args.all_sets,
+            args.sets.clone(),
+            args.skip_sets.clone(),
+            args.resume_build,
+        )?;
+        if args.all_sets {
+            add_additional_sets(sets_to_build, &get_additional_set_codes(), &args.skip_sets)
+        } else {
+            sets_to_build
+        }
+    } else {
+        let skip_sets: HashSet<String> = args.skip_sets.iter().map(|s| s.to_uppercase()).collect();
+        args.sets
+            .iter()
+            .map(|s| s.to_uppercase())
+            .filter(|s| !skip_sets.contains(s))
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect()
+    };
+
+    Ok(apply_resume_from_set(
+        sets_to_build,
+        args.resume_from_set.as_deref(),
+    ))
+}
+
+/// Write build_manifest.json listing every set built along with its hash
+pub fn write_build_manifest(
+    output_generator: &OutputGenerator,
+    args: &MtgjsonArgs,
+    built_sets: &[BuiltSetSummary],
+) -> PyResult<BuildManifest> {
+    let output_dir = Path::new(&output_generator.output_path);
+
+    let mut sets = Vec::with_capacity(built_sets.len());
+    for summary in built_sets {
+        let sha256 = output_generator
+            .calculate_file_hash(output_dir.join(&summary.file_name).display().to_string())?;
+        sets.push(BuildManifestSet {
+            summary: summary.clone(),
+            sha256,
+        });
+    }
+
+    let manifest = BuildManifest {
+        build_date: chrono::Utc::now().format("%Y-%m-%d").to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        arguments: args.clone(),
+        sets,
+    };
+
+    let manifest_json = if output_generator.pretty_print {
+        serde_json::to_string_pretty(&manifest)
+    } else {
+        serde_json::to_string(&manifest)
+    }
+    .map_err(|e| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Serialization error: {}", e))
+    })?;
+
+    fs::write(output_dir.join(BUILD_MANIFEST_FILE_NAME), manifest_json).map_err(|e| {
+        PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
+            "Failed to write build manifest: {}",
+            e
+        ))
+    })?;
+
+    Ok(manifest)
+}
+
+/// MTGJSON Dispatcher
+#[pyfunction]
+#[pyo3(signature = (args, output_path=None))]
+pub fn dispatcher(args: &MtgjsonArgs, output_path: Option<String>) -> PyResult<()> {
+    install_panic_hook();
+    configure_providers_config(args.providers_config.as_deref())?;
+    let notifications = BuildNotifications::from_config(args.no_alerts);
+    let build_start = Instant::now();
+    notifications.notify(&BuildEvent::Started {
+        set_count: (!args.all_sets).then_some(args.sets.len()),
+    });
+
+    let stats = match run_build(args, output_path) {
+        Ok(stats) => stats,
+        Err(e) => {
+            notifications.notify(&BuildEvent::Failed {
+                error: e.to_string(),
+                elapsed_secs: build_start.elapsed().as_secs_f64(),
+            });
+            return Err(e);
+        }
+    };
+    notifications.notify(&BuildEvent::Succeeded {
+        set_count: stats.sets_built,
+        elapsed_secs: stats.duration_secs,
+    });
+
+    if !args.quiet {
+        println!("{}", stats.summary());
+    }
+    Ok(())
+}
+
+static PANIC_HOOK: Once = Once::new();
+
+/// Flush buffered log records and build output before the default panic
+/// message, so a crash mid-build doesn't interleave with half-written lines.
+/// Installed once per process.
+pub fn install_panic_hook() {
+    PANIC_HOOK.call_once(|| install_panic_hook_with(flush_build_output));
+}
+
+/// Run `on_panic` ahead of whatever panic hook is currently installed
+pub fn install_panic_hook_with(on_panic: impl Fn() + Send + Sync + 'static) {
+    let previous_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        on_panic();
+        previous_hook(info);
+    }));
+}
+
+fn flush_build_output() {
+    log::logger().flush();
+    let _ = std::io::stdout().flush();
+    let _ = std::io::stderr().flush();
+}
+
+/// Point provider config loading at `--providers-config`, failing when that
+/// file can't be read rather than silently building without credentials
+fn configure_providers_config(providers_config: Option<&str>) -> PyResult<()> {
+    let config_path = providers_config.map(PathBuf::from);
+    if let Some(config_path) = &config_path {
+        MtgjsonConfig::from_path(Some(config_path)).map_err(|e| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Failed to load providers config {}: {}",
+                config_path.display(),
+                e
+            ))
+        })?;
+    }
+    set_providers_config_path(config_path);
+    Ok(())
+}
+
+/// Run the build the arguments ask for, returning its totals
+pub fn run_build(args: &MtgjsonArgs, output_path: Option<String>) -> PyResult<BuildStats> {
+    run_build_with(args, output_path, build_mtgjson_set)
+}
+
+/// Fetch today's prices from the live providers and merge them into the
+/// AllPrices archive in the output directory, streaming the archive so its
+/// full history is never held in memory
+fn build_and_write_prices(output_generator: &OutputGenerator, args: &MtgjsonArgs) -> PyResult<()> {
+    let output_dir = Path::new(&output_generator.output_path);
+    let all_printings_path = output_dir.join("AllPrintings.json");
+    if !all_printings_path.exists() {
+        return Err(PyErr::new::<pyo3::exceptions::PyFileNotFoundError, _>(
+            format!(
+                "Unable to build prices. AllPrintings not found in {:?}",
+                all_printings_path
+            ),
+        ));
+    }
+
+    let mut today_prices = build_today_prices_from_providers(&all_printings_path.to_string_lossy());
+    if let Some(since) = &args.since {
+        prune_price_data(&mut today_prices, since);
+    }
+
+    update_price_archive_file(
+        &output_dir.join("AllPrices.json"),
+        &today_prices,
+        DEFAULT_PRICE_RETENTION_DAYS,
+    )?;
+    output_generator.write_object_to_file("AllPricesToday", &today_prices, args.pretty)
+}
+
+/// `run_build`, taking each set from `build_set` instead of Scryfall
+pub fn run_build_with(
+    args: &MtgjsonArgs,
+    output_path: Option<String>,
+    build_set: impl Fn(&str) -> Option<MtgjsonSetObject>,
+) -> PyResult<BuildStats> {
+    let build_start = Instant::now();
+    let mut stats = BuildStats {
+        skipped_sets: args.skip_sets.clone(),
+        ..BuildStats::default()
+    };
+
+    set_response_dump_dir(args.debug_dump_responses.as_ref().map(PathBuf::from));
+    set_response_cache((!args.no_cache).then(|| {
+        ResponseCache::with_ttl(Duration::from_secs(
+            args.cache_ttl.unwrap_or(DEFAULT_CACHE_TTL_SECS),
+        ))
+    }));
+
+    let default_retry_config = RetryConfig::default();
+    set_retry_config(RetryConfig {
+        max_retries: args.max_retries.unwrap_or(default_retry_config.max_retries),
+        base_ms: args.retry_base_ms.unwrap_or(default_retry_config.base_ms),
+    });
+    set_skipped_enrichment(args.get_skipped_enrichment());
+    load_all_resources();
+
+    let mut output_generator = OutputGenerator::new(output_path, Some(args.pretty));
+    output_generator.no_monolith = args.no_monolith;
+    output_generator.skip_compiled = args.skip_compiled;
+    output_generator.skip_alt_formats = args.skip_alt_formats;
+    output_generator.sort_keys = args.sort_keys;
+    output_generator.indent = args.indent.unwrap_or(DEFAULT_INDENT);
+
+    if args.price_build {
+        build_and_write_prices(&output_generator, args)?;
+        stats.duration_secs = build_start.elapsed().as_secs_f64();
+        return Ok(stats);
+    }
+
+    let mut card_prices = None;
+    if let Some(prices_input) = &args.prices_input {
+        let (all_prices, today_prices) = build_prices_from_archive(
+            Path::new(prices_input),
+            DEFAULT_PRICE_RETENTION_DAYS,
+            args.since.as_deref(),
+        )?;
+        output_generator.write_compiled_prices_output(&all_prices, &today_prices, args.pretty)?;
+        if !args.include_prices_in_cards {
+            stats.duration_secs = build_start.elapsed().as_secs_f64();
+            return Ok(stats);
+        }
+        // Keep building sets, embedding these prices in their cards
+        card_prices = Some(today_prices);
+    }
+
+    let sets_to_build = get_sets_to_build(args)?;
+    let built_sets = if sets_to_build.is_empty() {
+        Vec::new()
+    } else {
+        build_and_write_mtgjson_sets(
+            &sets_to_build,
+            &output_generator,
+            &args.set_code_remap,
+            &args.additional_cards_files,
+            args.emit_both,
+            args.strict_uuid,
+            args.resume_build && !args.full_build,
+            args.get_card_sort_order()?,
+            card_prices.as_ref(),
+            &mut stats,
+            build_set,
+        )?
+    };
+
+    if args.full_build {
+        output_generator.generate_compiled_output_files(Some(args.pretty))?;
+    }
+
+    if args.write_manifest {
+        write_build_manifest(&output_generator, args, &built_sets)?;
+    }
+
+    if args.compress {
+        compress_output(
+            Path::new(&output_generator.output_path),
+            &args.get_compression_formats()?,
+        )
+        .map_err(|e| {
+            PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
+                "Failed to compress output: {}",
+                e
+            ))
+        })?;
+    }
+
+    if let Some(bucket) = &args.aws_s3_upload_bucket {
+        let output_dir = Path::new(&output_generator.output_path);
+        let prefix = args.aws_s3_upload_prefix.as_deref();
+        if args.aws_s3_dry_run {
+            dry_run_s3_upload(bucket, output_dir, prefix)?;
+        } else {
+            upload_to_s3(bucket, output_dir, prefix)?;
+        }
+    }
+
+    stats.duration_secs = build_start.elapsed().as_secs_f64();
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_output_dir(name: &str) -> std::path::PathBuf {
+        let output_dir = std::env::temp_dir().join(name);
+        let _ = fs::remove_dir_all(&output_dir);
+        output_dir
+    }
+
+    /// A small local set standing in for a Scryfall download: two cards and
+    /// a token
+    fn fixture_set(set_code: &str) -> Option<MtgjsonSetObject> {
+        use crate::classes::MtgjsonCardObject;
+
+        let set_code = set_code.to_uppercase();
+        let mut mtgjson_set = MtgjsonSetObject::new();
+        mtgjson_set.code = Some(set_code.clone());
+        mtgjson_set.name = format!("{} Fixture", set_code);
+        mtgjson_set.release_date = "2021-01-01".to_string();
+        for (name, number) in [("Llanowar Elves", "2"), ("Fyndhorn Elves", "1")] {
+            mtgjson_set.cards.push(
+                MtgjsonCardObject::builder()
+                    .name(name)
+                    .set_code(&set_code)
+                    .number(number)
+                    .scryfall_id(&format!("{}-{}", set_code, number))
+                    .build(),
+            );
+        }
+        mtgjson_set.tokens.push(
+            MtgjsonCardObject::builder()
+                .is_token(true)
+                .name("Elf Warrior")
+                .set_code(&format!("T{}", set_code))
+                .number("1")
+                .scryfall_id(&format!("T{}-1", set_code))
+                .build(),
+        );
+        Some(mtgjson_set)
+    }
+
+    #[test]
+    fn test_panic_hook_runs_on_panic_in_child_thread() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::Arc;
+
+        let hook_ran = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&hook_ran);
+        install_panic_hook_with(move || {
+            if std::thread::current().name() == Some("panicking-build") {
+                flag.store(true, Ordering::SeqCst);
+            }
+        });
+
+        let result = std::thread::Builder::new()
+            .name("panicking-build".to_string())
+            .spawn(|| panic!("forced build panic"))
+            .unwrap()
+            .join();
+        assert!(result.is_err());
+        assert!(hook_ran.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_get_sets_to_build_resume_from_set() {
+        let mut args = MtgjsonArgs::new();
+        args.sets = ["M21", "DOM", "KHM", "C21", "ZNR"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        args.skip_sets = vec!["ZNR".to_string()];
+        args.resume_from_set = Some("dom".to_string());
+
+        assert_eq!(get_sets_to_build(&args).unwrap(), vec!["DOM", "KHM", "M21"]);
+
+        // A code not in the list resumes from where it would sort
+        args.resume_from_set = Some("E".to_string());
+        assert_eq!(get_sets_to_build(&args).unwrap(), vec!["KHM", "M21"]);
+    }
+
+    #[test]
+    fn test_additional_sets_join_the_all_sets_build() {
+        let scryfall_sets = vec!["DOM".to_string(), "M21".to_string()];
+        let additional_sets: HashSet<String> = ["dd3", "Q01", "DOM"]
+            .iter()
+            .map(|s| s.to_uppercase())
+            .collect();
+
+        let mut sets_to_build =
+            add_additional_sets(scryfall_sets, &additional_sets, &["q01".to_string()]);
+        sets_to_build.sort();
+        assert_eq!(sets_to_build, vec!["DD3", "DOM", "M21"]);
+    }
+
+    #[test]
+    fn test_build_mtgjson_sets_with_set_code_remap() {
+        let output_dir = test_output_dir("mtgjson_set_code_remap_test");
+        let output_generator = OutputGenerator::new(Some(output_dir.display().to_string()), None);
+
+        let mut remap = HashMap::new();
+        remap.insert("PDOM".to_string(), "DOMP".to_string());
+
+        build_and_write_mtgjson_sets(
+            &["PDOM".to_string(), "M21".to_string()],
+            &output_generator,
+            &remap,
+            &HashMap::new(),
+            false,
+            false,
+            false,
+            CardSortOrder::default(),
+            None,
+            &mut BuildStats::default(),
+            fixture_set,
+        )
+        .unwrap();
+
+        let read_set = |name: &str| -> serde_json::Value {
+            let contents = fs::read_to_string(output_dir.join(name)).unwrap();
+            serde_json::from_str(&contents).unwrap()
+        };
+
+        // Built from PDOM's data, written out under DOMP
+        assert!(!output_dir.join("PDOM.json").exists());
+        let remapped = read_set("DOMP.json");
+        assert_eq!(remapped["data"]["code"], "DOMP");
+        assert_eq!(remapped["data"]["name"], "PDOM Fixture");
+        assert_eq!(remapped["data"]["cards"].as_array().unwrap().len(), 2);
+
+        // Sets without a remap keep their code
+        assert_eq!(read_set("M21.json")["data"]["code"], "M21");
+
+        let _ = fs::remove_dir_all(&output_dir);
+    }
+
+    #[test]
+    fn test_should_rebuild_set_when_resuming() {
+        let output_dir = test_output_dir("mtgjson_should_rebuild_test");
+        fs::create_dir_all(&output_dir).unwrap();
+        let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
+
+        // Missing
+        assert!(should_rebuild_set(&output_dir, "TST", true));
+
+        // Fresh
+        let written = |date: &str| {
+            serde_json::json!({"meta": {"date": date, "version": "5.2.2"}, "data": {}}).to_string()
+        };
+        fs::write(output_dir.join("TST.json"), written(&today)).unwrap();
+        assert!(!should_rebuild_set(&output_dir, "TST", true));
+        assert!(should_rebuild_set(&output_dir, "TST", false));
+
+        // Stale
+        fs::write(output_dir.join("TST.json"), written("2020-01-01")).unwrap();
+        assert!(should_rebuild_set(&output_dir, "TST", true));
+
+        // Unparseable
+        fs::write(output_dir.join("TST.json"), b"{\"meta\": {\"date\"").unwrap();
+        assert!(should_rebuild_set(&output_dir, "TST", true));
+
+        let _ = fs::remove_dir_all(&output_dir);
+    }
+
+    #[test]
+    fn test_build_mtgjson_sets_emit_both() {
+        let output_dir = test_output_dir("mtgjson_emit_both_test");
+        let output_generator = OutputGenerator::new(Some(output_dir.display().to_string()), None);
+
+        build_and_write_mtgjson_sets(
+            &["TST".to_string()],
+            &output_generator,
+            &HashMap::new(),
+            &HashMap::new(),
+            true,
+            false,
+            false,
+            CardSortOrder::default(),
+            None,
+            &mut BuildStats::default(),
+            fixture_set,
+        )
+        .unwrap();
+
+        let minified = fs::read_to_string(output_dir.join("TST.json")).unwrap();
+        let pretty = fs::read_to_string(output_dir.join("TST.pretty.json")).unwrap();
+        assert!(!minified.contains('\n'));
+        assert!(pretty.contains('\n'));
+
+        let minified: serde_json::Value = serde_json::from_str(&minified).unwrap();
+        let pretty: serde_json::Value = serde_json::from_str(&pretty).unwrap();
+        assert_eq!(minified["data"], pretty["data"]);
+
+        let _ = fs::remove_dir_all(&output_dir);
+    }
+
+    #[test]
+    fn test_dispatcher_writes_build_manifest() {
+        let output_dir = test_output_dir("mtgjson_build_manifest_test");
+
+        let mut args = MtgjsonArgs::new();
+        args.sets = vec!["TST".to_string()];
+        args.write_manifest = true;
+
+        run_build_with(&args, Some(output_dir.display().to_string()), fixture_set).unwrap();
+
+        let contents = fs::read_to_string(output_dir.join(BUILD_MANIFEST_FILE_NAME)).unwrap();
+        let manifest: serde_json::Value = serde_json::from_str(&contents).unwrap();
+
+        assert_eq!(manifest["version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(manifest["arguments"]["sets"][0], "TST");
+
+        let sets = manifest["sets"].as_array().unwrap();
+        assert_eq!(sets.len(), 1);
+        assert_eq!(sets[0]["code"], "TST");
+        assert_eq!(sets[0]["file_name"], "TST.json");
+
+        let generator = OutputGenerator::new(Some(output_dir.display().to_string()), None);
+        let expected_hash = generator
+            .calculate_file_hash(output_dir.join("TST.json").display().to_string())
+            .unwrap();
+        assert_eq!(sets[0]["sha256"], expected_hash);
+        assert_eq!(expected_hash.len(), 64);
+
+        let _ = fs::remove_dir_all(&output_dir);
+    }
+
+    #[test]
+    fn test_run_build_reports_stats() {
+        let output_dir = test_output_dir("mtgjson_build_stats_test");
+
+        let mut args = MtgjsonArgs::new();
+        args.sets = vec!["TST".to_string(), "TSU".to_string(), "ZNR".to_string()];
+        args.skip_sets = vec!["ZNR".to_string()];
+
+        let stats =
+            run_build_with(&args, Some(output_dir.display().to_string()), fixture_set).unwrap();
+
+        assert!(output_dir.join("TST.json").exists());
+        assert!(output_dir.join("TSU.json").exists());
+        assert!(!output_dir.join("ZNR.json").exists());
+
+        assert_eq!(stats.sets_built, 2);
+        assert_eq!(stats.total_cards, 4);
+        assert_eq!(stats.total_tokens, 2);
+        assert_eq!(stats.total_decks, 0);
+        assert_eq!(stats.total_sealed_products, 0);
+        assert_eq!(stats.skipped_sets, vec!["ZNR"]);
+        assert!(stats.failed_sets.is_empty());
+
+        let summary = stats.summary();
+        assert!(summary.contains("Sets built: 2"));
+        assert!(summary.contains("Cards: 4"));
+        assert!(summary.contains("Skipped sets: ZNR"));
+        assert!(summary.contains("Failed sets: none"));
+
+        let _ = fs::remove_dir_all(&output_dir);
+    }
+
+    #[test]
+    fn test_run_build_reports_failed_sets() {
+        let output_dir = test_output_dir("mtgjson_build_failed_sets_test");
+
+        let mut args = MtgjsonArgs::new();
+        args.sets = vec!["TST".to_string(), "BAD".to_string()];
+
+        let build_set = |set_code: &str| {
+            if set_code == "BAD" {
+                None
+            } else {
+                fixture_set(set_code)
+            }
+        };
+        let stats =
+            run_build_with(&args, Some(output_dir.display().to_string()), build_set).unwrap();
+
+        assert!(output_dir.join("TST.json").exists());
+        assert!(!output_dir.join("BAD.json").exists());
+        assert_eq!(stats.sets_built, 1);
+        assert_eq!(stats.failed_sets, vec!["BAD"]);
+        assert!(stats.summary().contains("Failed sets: BAD"));
+
+        let _ = fs::remove_dir_all(&output_dir);
+    }
+
+    #[test]
+    fn test_dispatcher_assembles_all_printings_from_set_files() {
+        let output_dir = test_output_dir("mtgjson_all_printings_test");
+
+        let mut args = MtgjsonArgs::new();
+        args.sets = vec!["TST".to_string()];
+        args.full_build = true;
+
+        run_build_with(&args, Some(output_dir.display().to_string()), fixture_set).unwrap();
+
+        let contents = fs::read_to_string(output_dir.join("AllPrintings.json")).unwrap();
+        let all_printings: serde_json::Value = serde_json::from_str(&contents).unwrap();
+        assert_eq!(all_printings["data"]["TST"]["code"], "TST");
+
+        let _ = fs::remove_dir_all(&output_dir);
+    }
+
+    #[test]
+    fn test_dispatcher_no_monolith() {
+        let output_dir = test_output_dir("mtgjson_no_monolith_test");
+
+        let mut args = MtgjsonArgs::new();
+        args.sets = vec!["TST".to_string()];
+        args.full_build = true;
+        args.no_monolith = true;
+
+        run_build_with(&args, Some(output_dir.display().to_string()), fixture_set).unwrap();
+
+        assert!(output_dir.join("TST.json").exists());
+        assert!(!output_dir.join("AllPrintings.json").exists());
+
+        let _ = fs::remove_dir_all(&output_dir);
+    }
+
+    #[test]
+    fn test_dispatcher_skip_compiled() {
+        let output_dir = test_output_dir("mtgjson_skip_compiled_dispatch_test");
+
+        // A set file already on disk, so nothing is downloaded
+        let output_generator = OutputGenerator::new(Some(output_dir.display().to_string()), None);
+        write_set_file(
+            &output_generator,
+            "TST",
+            &fixture_set("TST").unwrap(),
+            false,
+        )
+        .unwrap();
+
+        // A price archive from an earlier price build
+        let all_prices = serde_json::json!({
+            "uuid-1": {"paper": {"tcgplayer": {"retail": {"normal": {"2024-03-01": 1.0}}}}}
+        });
+        output_generator
+            .write_object_to_file("AllPrices", &all_prices, false)
+            .unwrap();
+
+        let mut args = MtgjsonArgs::new();
+        args.full_build = true;
+        args.skip_compiled = true;
+
+        dispatcher(&args, Some(output_dir.display().to_string())).unwrap();
+
+        assert!(output_dir.join("TST.json").exists());
+        let contents = fs::read_to_string(output_dir.join("AllPrices.json")).unwrap();
+        let archive: serde_json::Value = serde_json::from_str(&contents).unwrap();
+        assert_eq!(archive["data"], all_prices);
+        assert!(!output_dir.join("AllPrintings.json").exists());
+        assert!(!output_dir.join("AtomicCards.json").exists());
+
+        let _ = fs::remove_dir_all(&output_dir);
+    }
+
+    #[test]
+    fn test_dispatcher_prices_input() {
+        let output_dir = test_output_dir("mtgjson_prices_input_test");
+        fs::create_dir_all(&output_dir).unwrap();
+
+        let date = |days_ago: i64| {
+            (chrono::Utc::now() - chrono::Duration::days(days_ago))
+                .format("%Y-%m-%d")
+                .to_string()
+        };
+        let (today, yesterday, stale) = (date(0), date(1), date(365));
+
+        let archive = serde_json::json!({
+            "meta": {"date": yesterday, "version": "5.2.2"},
+            "data": {
+                "card-uuid": {
+                    "paper": {
+                        "tcgplayer": {
+                            "currency": "USD",
+                            "retail": {
+                                "normal": {&stale: 0.5, &yesterday: 1.0, &today: 1.25}
+                            }
+                        }
+                    }
+                },
+                "old-card-uuid": {
+                    "paper": {
+                        "tcgplayer": {"currency": "USD", "retail": {"normal": {&stale: 9.0}}}
+                    }
+                }
+            }
+        });
+        let archive_path = output_dir.join("AllPricesFixture.json");
+        fs::write(&archive_path, archive.to_string()).unwrap();
+
+        let mut args = MtgjsonArgs::new();
+        args.prices_input = Some(archive_path.display().to_string());
+
+        dispatcher(&args, Some(output_dir.display().to_string())).unwrap();
+
+        let read_output = |name: &str| -> serde_json::Value {
+            let contents = fs::read_to_string(output_dir.join(name)).unwrap();
+            serde_json::from_str(&contents).unwrap()
+        };
+
+        let all_prices = read_output("AllPrices.json");
+        let series = &all_prices["data"]["card-uuid"]["paper"]["tcgplayer"];
+        assert_eq!(series["currency"], "USD");
+        assert_eq!(
+            series["retail"]["normal"],
+            serde_json::json!({&yesterday: 1.0, &today: 1.25})
+        );
+        assert!(all_prices["data"].get("old-card-uuid").is_none());
+
+        let today_prices = read_output("AllPricesToday.json");
+        assert_eq!(
+            today_prices["data"]["card-uuid"]["paper"]["tcgplayer"]["retail"]["normal"],
+            serde_json::json!({&today: 1.25})
+        );
+        assert!(today_prices["data"].get("old-card-uuid").is_none());
+        assert!(!output_dir.join("AllPrintings.json").exists());
+
+        let _ = fs::remove_dir_all(&output_dir);
+    }
+}
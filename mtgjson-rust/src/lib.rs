@@ -22,6 +22,8 @@ impl JsonValue {
     }
 }
 
+// Constants shared by every module
+mod constants;
 // Classes module
 mod classes;
 // Providers module
@@ -30,6 +32,10 @@ mod providers;
 mod compiled_classes;
 // Performance modules
 mod builders;
+// Command line and build orchestration
+mod arg_parser;
+mod dispatcher;
+mod notifications;
 
 // Import all classes
 use classes::{
@@ -54,7 +60,8 @@ use providers::{
 use compiled_classes::{
     MtgjsonAllIdentifiers, MtgjsonAllPrintings, MtgjsonAtomicCards, MtgjsonCardTypesObject,
     MtgjsonCompiledList, MtgjsonDeckObjectList, MtgjsonEnumValues, MtgjsonKeywords,
-    MtgjsonSetObjectList, MtgjsonStructures, MtgjsonTcgplayerSkus,
+    MtgjsonSetListEntry, MtgjsonSetObjectList, MtgjsonStructures, MtgjsonTcgplayerSku,
+    MtgjsonTcgplayerSkus,
 };
 
 // Import all performance modules
@@ -104,7 +111,9 @@ fn mtgjson_rust(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<MtgjsonAtomicCards>()?;
     m.add_class::<MtgjsonCardTypesObject>()?;
     m.add_class::<MtgjsonEnumValues>()?;
+    m.add_class::<MtgjsonSetListEntry>()?;
     m.add_class::<MtgjsonSetObjectList>()?;
+    m.add_class::<MtgjsonTcgplayerSku>()?;
     m.add_class::<MtgjsonTcgplayerSkus>()?;
 
     // Add high-performance classes
@@ -163,6 +172,12 @@ fn mtgjson_rust(m: &Bound<'_, PyModule>) -> PyResult<()> {
         m
     )?)?;
 
+    // Add command line and build orchestration
+    m.add_class::<arg_parser::MtgjsonArgs>()?;
+    m.add_function(wrap_pyfunction!(arg_parser::parse_args, m)?)?;
+    m.add_function(wrap_pyfunction!(dispatcher::build_mtgjson_sets, m)?)?;
+    m.add_function(wrap_pyfunction!(dispatcher::dispatcher, m)?)?;
+
     // Add all provider classes for 100% Python API coverage
     providers::add_provider_classes_to_module(m)?;
 
@@ -0,0 +1,261 @@
+// Build alerts - Rust port of `mtgjson5.utils.send_push_notification`
+use serde_json::{json, Value};
+
+use crate::builders::runtime::block_on_shared;
+use crate::providers::scryfall::sf_utils::MtgjsonConfig;
+use crate::providers::{ProviderError, ProviderResult};
+
+/// A point in the build worth alerting maintainers about
+#[derive(Debug, Clone, PartialEq)]
+pub enum BuildEvent {
+    /// `set_count` is `None` when every set is being built
+    Started {
+        set_count: Option<usize>,
+    },
+    Succeeded {
+        set_count: usize,
+        elapsed_secs: f64,
+    },
+    Failed {
+        error: String,
+        elapsed_secs: f64,
+    },
+}
+
+impl BuildEvent {
+    /// Message text sent for this event
+    pub fn format_message(&self) -> String {
+        match self {
+            BuildEvent::Started { set_count: None } => {
+                "MTGJSON build started for all sets".to_string()
+            }
+            BuildEvent::Started {
+                set_count: Some(set_count),
+            } => format!("MTGJSON build started for {} sets", set_count),
+            BuildEvent::Succeeded {
+                set_count,
+                elapsed_secs,
+            } => format!(
+                "MTGJSON build finished: {} sets in {:.1}s",
+                set_count, elapsed_secs
+            ),
+            BuildEvent::Failed {
+                error,
+                elapsed_secs,
+            } => format!("MTGJSON build failed after {:.1}s: {}", elapsed_secs, error),
+        }
+    }
+}
+
+/// Somewhere build alerts can be delivered
+pub trait Notifier: Send + Sync {
+    /// Name used when reporting a failed delivery
+    fn name(&self) -> &str;
+
+    /// Deliver `message`
+    fn send(&self, message: &str) -> ProviderResult<()>;
+}
+
+/// POST `payload` as JSON to a webhook
+fn post_webhook(webhook_url: &str, payload: &Value) -> ProviderResult<()> {
+    block_on_shared(async {
+        let response = reqwest::Client::new()
+            .post(webhook_url)
+            .json(payload)
+            .send()
+            .await
+            .map_err(|e| ProviderError::NetworkError(format!("Request failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(ProviderError::NetworkError(format!(
+                "HTTP error {}",
+                response.status().as_u16()
+            )));
+        }
+        Ok(())
+    })
+}
+
+/// Posts alerts to a Slack incoming webhook
+pub struct SlackNotifier {
+    pub webhook_url: String,
+}
+
+impl SlackNotifier {
+    /// Request body Slack expects for `message`
+    pub fn payload(message: &str) -> Value {
+        json!({ "text": message })
+    }
+}
+
+impl Notifier for SlackNotifier {
+    fn name(&self) -> &str {
+        "Slack"
+    }
+
+    fn send(&self, message: &str) -> ProviderResult<()> {
+        post_webhook(&self.webhook_url, &Self::payload(message))
+    }
+}
+
+/// Posts alerts to a Discord webhook
+pub struct DiscordNotifier {
+    pub webhook_url: String,
+}
+
+impl DiscordNotifier {
+    /// Request body Discord expects for `message`
+    pub fn payload(message: &str) -> Value {
+        json!({ "content": message })
+    }
+}
+
+impl Notifier for DiscordNotifier {
+    fn name(&self) -> &str {
+        "Discord"
+    }
+
+    fn send(&self, message: &str) -> ProviderResult<()> {
+        post_webhook(&self.webhook_url, &Self::payload(message))
+    }
+}
+
+/// Every configured notifier. Empty when alerts are disabled, in which case
+/// notifying does nothing.
+#[derive(Default)]
+pub struct BuildNotifications {
+    notifiers: Vec<Box<dyn Notifier>>,
+}
+
+impl BuildNotifications {
+    pub fn new(notifiers: Vec<Box<dyn Notifier>>) -> Self {
+        Self { notifiers }
+    }
+
+    /// Notifiers for each webhook in the `[Slack]` and `[Discord]` sections
+    /// of the config, or none with `no_alerts`
+    pub fn from_config(no_alerts: bool) -> Self {
+        if no_alerts {
+            return Self::default();
+        }
+        let config = match MtgjsonConfig::new() {
+            Ok(config) => config,
+            Err(_) => return Self::default(),
+        };
+        let webhook_url = |section: &str| {
+            config
+                .get_string(section, "webhook_url")
+                .ok()
+                .filter(|url| !url.is_empty())
+        };
+
+        let mut notifiers: Vec<Box<dyn Notifier>> = Vec::new();
+        if let Some(webhook_url) = webhook_url("Slack") {
+            notifiers.push(Box::new(SlackNotifier { webhook_url }));
+        }
+        if let Some(webhook_url) = webhook_url("Discord") {
+            notifiers.push(Box::new(DiscordNotifier { webhook_url }));
+        }
+        Self::new(notifiers)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.notifiers.is_empty()
+    }
+
+    /// Send `event` to every notifier. A failed delivery is logged and never
+    /// fails the build.
+    pub fn notify(&self, event: &BuildEvent) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        let message = event.format_message();
+        for notifier in &self.notifiers {
+            if let Err(e) = notifier.send(&message) {
+                eprintln!(
+                    "Warning: Failed to send {} notification: {}",
+                    notifier.name(),
+                    e
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    struct RecordingNotifier {
+        sent: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Notifier for RecordingNotifier {
+        fn name(&self) -> &str {
+            "Recording"
+        }
+
+        fn send(&self, message: &str) -> ProviderResult<()> {
+            self.sent.lock().unwrap().push(message.to_string());
+            Err(ProviderError::NetworkError("HTTP error 500".to_string()))
+        }
+    }
+
+    #[test]
+    fn test_build_event_messages() {
+        assert_eq!(
+            BuildEvent::Started { set_count: Some(3) }.format_message(),
+            "MTGJSON build started for 3 sets"
+        );
+        assert_eq!(
+            BuildEvent::Started { set_count: None }.format_message(),
+            "MTGJSON build started for all sets"
+        );
+        assert_eq!(
+            BuildEvent::Succeeded {
+                set_count: 12,
+                elapsed_secs: 83.25
+            }
+            .format_message(),
+            "MTGJSON build finished: 12 sets in 83.2s"
+        );
+        assert_eq!(
+            BuildEvent::Failed {
+                error: "Scryfall is down".to_string(),
+                elapsed_secs: 4.0
+            }
+            .format_message(),
+            "MTGJSON build failed after 4.0s: Scryfall is down"
+        );
+        assert_eq!(
+            SlackNotifier::payload("hi"),
+            serde_json::json!({"text": "hi"})
+        );
+        assert_eq!(
+            DiscordNotifier::payload("hi"),
+            serde_json::json!({"content": "hi"})
+        );
+    }
+
+    #[test]
+    fn test_disabled_notifications_are_a_no_op() {
+        let notifications = BuildNotifications::from_config(true);
+        assert!(!notifications.is_enabled());
+        notifications.notify(&BuildEvent::Started { set_count: None });
+    }
+
+    #[test]
+    fn test_failed_delivery_is_not_fatal() {
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let notifications = BuildNotifications::new(vec![Box::new(RecordingNotifier {
+            sent: Arc::clone(&sent),
+        })]);
+        notifications.notify(&BuildEvent::Started { set_count: Some(1) });
+        assert_eq!(
+            *sent.lock().unwrap(),
+            vec!["MTGJSON build started for 1 sets".to_string()]
+        );
+    }
+}
@@ -0,0 +1,242 @@
+// Upload finished builds to AWS S3
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use aws_sdk_s3::primitives::ByteStream;
+
+use super::provider_base::{retry_config, with_retries};
+use super::{ProviderError, ProviderResult};
+use crate::builders::runtime::block_on_shared;
+
+/// A single file to upload and where it goes in the bucket
+#[derive(Debug, Clone, PartialEq)]
+pub struct S3Upload {
+    pub path: PathBuf,
+    pub key: String,
+    pub content_type: &'static str,
+}
+
+/// Content type for an output file, covering the JSON outputs and their
+/// compressed variants
+pub fn content_type_for(path: &Path) -> &'static str {
+    match path.extension().and_then(|s| s.to_str()) {
+        Some("json") => "application/json",
+        Some("gz") => "application/gzip",
+        Some("bz2") => "application/x-bzip2",
+        Some("xz") => "application/x-xz",
+        Some("zip") => "application/zip",
+        Some("csv") => "text/csv",
+        Some("sha256") | Some("txt") => "text/plain",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Object key for `path`: its path relative to `output_dir`, with `/`
+/// separators, under `prefix` when one is given
+pub fn s3_key(output_dir: &Path, path: &Path, prefix: Option<&str>) -> String {
+    let relative_path = path.strip_prefix(output_dir).unwrap_or(path);
+    let relative_key = relative_path
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+
+    match prefix
+        .map(|p| p.trim_matches('/'))
+        .filter(|p| !p.is_empty())
+    {
+        Some(prefix) => format!("{}/{}", prefix, relative_key),
+        None => relative_key,
+    }
+}
+
+/// Every file under `dir`, recursively
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> ProviderResult<()> {
+    let entries = fs::read_dir(dir)
+        .map_err(|e| ProviderError::ProcessingError(format!("Failed to read {:?}: {}", dir, e)))?;
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Work out every upload for the output directory, sorted by key
+pub fn plan_s3_uploads(output_dir: &Path, prefix: Option<&str>) -> ProviderResult<Vec<S3Upload>> {
+    let mut files = Vec::new();
+    collect_files(output_dir, &mut files)?;
+
+    let mut uploads: Vec<S3Upload> = files
+        .into_iter()
+        .map(|path| S3Upload {
+            key: s3_key(output_dir, &path, prefix),
+            content_type: content_type_for(&path),
+            path,
+        })
+        .collect();
+    uploads.sort_by(|a, b| a.key.cmp(&b.key));
+    Ok(uploads)
+}
+
+/// Log every upload `upload_to_s3` would make without transferring anything
+pub fn dry_run_s3_upload(
+    bucket: &str,
+    output_dir: &Path,
+    prefix: Option<&str>,
+) -> ProviderResult<Vec<S3Upload>> {
+    let uploads = plan_s3_uploads(output_dir, prefix)?;
+    for upload in &uploads {
+        println!(
+            "[dry run] Would upload {:?} to s3://{}/{} ({})",
+            upload.path, bucket, upload.key, upload.content_type
+        );
+    }
+    Ok(uploads)
+}
+
+/// Upload a single file, retrying transient failures. Server errors and
+/// dropped connections are retried; 4xx responses are not.
+async fn put_object(
+    client: &aws_sdk_s3::Client,
+    bucket: &str,
+    upload: &S3Upload,
+) -> ProviderResult<()> {
+    with_retries(retry_config(), || async {
+        let body = ByteStream::from_path(&upload.path).await.map_err(|e| {
+            ProviderError::ProcessingError(format!("Failed to read {:?}: {}", upload.path, e))
+        })?;
+
+        client
+            .put_object()
+            .bucket(bucket)
+            .key(&upload.key)
+            .content_type(upload.content_type)
+            .body(body)
+            .send()
+            .await
+            .map(|_| ())
+            .map_err(|e| match e.raw_response().map(|r| r.status().as_u16()) {
+                Some(status) => ProviderError::NetworkError(format!(
+                    "HTTP error {} uploading {}: {}",
+                    status, upload.key, e
+                )),
+                None => {
+                    ProviderError::NetworkError(format!("Failed to upload {}: {}", upload.key, e))
+                }
+            })
+    })
+    .await
+}
+
+/// Upload every file in the output directory to `bucket`, preserving
+/// relative paths. Credentials come from the standard AWS environment.
+pub async fn upload_to_s3_async(
+    bucket: &str,
+    output_dir: &Path,
+    prefix: Option<&str>,
+) -> ProviderResult<()> {
+    let uploads = plan_s3_uploads(output_dir, prefix)?;
+
+    let config = aws_config::load_from_env().await;
+    let client = aws_sdk_s3::Client::new(&config);
+
+    println!("Uploading {} files to s3://{}", uploads.len(), bucket);
+    for upload in &uploads {
+        put_object(&client, bucket, upload).await?;
+    }
+    println!("Finished uploading to s3://{}", bucket);
+
+    Ok(())
+}
+
+/// Upload every file in the output directory to `bucket` (main public interface)
+pub fn upload_to_s3(
+    bucket: &str,
+    output_dir: &Path,
+    prefix: Option<&str>,
+) -> Result<(), ProviderError> {
+    block_on_shared(upload_to_s3_async(bucket, output_dir, prefix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_s3_key_preserves_relative_paths() {
+        let output_dir = Path::new("/tmp/mtgjson_build");
+        assert_eq!(
+            s3_key(output_dir, &output_dir.join("AllPrintings.json"), None),
+            "AllPrintings.json"
+        );
+        assert_eq!(
+            s3_key(
+                output_dir,
+                &output_dir.join("decks").join("Deck_TST.json"),
+                None
+            ),
+            "decks/Deck_TST.json"
+        );
+        assert_eq!(
+            s3_key(
+                output_dir,
+                &output_dir.join("SetList.json"),
+                Some("/api/v5/")
+            ),
+            "api/v5/SetList.json"
+        );
+        assert_eq!(
+            s3_key(output_dir, &output_dir.join("Meta.json"), Some("")),
+            "Meta.json"
+        );
+    }
+
+    #[test]
+    fn test_content_type_for_outputs() {
+        assert_eq!(content_type_for(Path::new("TST.json")), "application/json");
+        assert_eq!(
+            content_type_for(Path::new("TST.json.gz")),
+            "application/gzip"
+        );
+        assert_eq!(
+            content_type_for(Path::new("TST.json.bz2")),
+            "application/x-bzip2"
+        );
+        assert_eq!(
+            content_type_for(Path::new("TST.json.xz")),
+            "application/x-xz"
+        );
+        assert_eq!(
+            content_type_for(Path::new("TST.json.zip")),
+            "application/zip"
+        );
+        assert_eq!(
+            content_type_for(Path::new("build_manifest")),
+            "application/octet-stream"
+        );
+    }
+
+    #[test]
+    fn test_dry_run_plans_every_file() {
+        let output_dir = std::env::temp_dir().join("mtgjson_s3_dry_run_test");
+        let _ = fs::remove_dir_all(&output_dir);
+        fs::create_dir_all(output_dir.join("decks")).unwrap();
+        fs::write(output_dir.join("TST.json"), "{}").unwrap();
+        fs::write(output_dir.join("TST.json.gz"), "").unwrap();
+        fs::write(output_dir.join("decks").join("Deck_TST.json"), "{}").unwrap();
+
+        let uploads = dry_run_s3_upload("mtgjson-test", &output_dir, Some("v5")).unwrap();
+        let keys: Vec<&str> = uploads.iter().map(|u| u.key.as_str()).collect();
+        assert_eq!(
+            keys,
+            vec!["v5/TST.json", "v5/TST.json.gz", "v5/decks/Deck_TST.json"]
+        );
+        assert_eq!(uploads[1].content_type, "application/gzip");
+
+        let _ = fs::remove_dir_all(&output_dir);
+    }
+}
@@ -1,3 +1,5 @@
+use crate::builders::resources::load_resource;
+use crate::builders::runtime::block_on_shared;
 use crate::classes::MtgjsonPricesObject;
 use crate::providers::{AbstractProvider, BaseProvider, ProviderResult};
 use async_trait::async_trait;
@@ -103,8 +105,7 @@ impl CardMarketProvider {
             return Ok(empty_dict.into());
         }
 
-        let rt = tokio::runtime::Runtime::new().unwrap();
-        let result = rt.block_on(async {
+        let result = block_on_shared(async {
             let mut request_builder = self.client.get(&url);
 
             if let Some(params) = params {
@@ -291,8 +292,7 @@ impl CardMarketProvider {
         }
         let mcm_id = mcm_id.unwrap();
 
-        let rt = tokio::runtime::Runtime::new().unwrap();
-        let result = rt.block_on(async {
+        let result = block_on_shared(async {
             // Retry logic - try up to 5 times with delays
             for attempt in 0..5 {
                 match self.fetch_mkm_cards(mcm_id).await {
@@ -518,8 +518,7 @@ impl CardMarketProvider {
             return Ok(());
         }
 
-        let rt = tokio::runtime::Runtime::new().unwrap();
-        rt.block_on(async {
+        block_on_shared(async {
             let expansions_url =
                 "https://api.cardmarket.com/ws/v2.0/expansions/1/singles".to_string();
 
@@ -600,22 +599,7 @@ impl CardMarketProvider {
 
     /// Load MKM set name fixes from resource file
     fn load_mkm_set_name_fixes() -> HashMap<String, String> {
-        let resource_path = std::env::current_dir()
-            .unwrap_or_else(|_| std::path::PathBuf::from("."))
-            .join("mtgjson5")
-            .join("resources")
-            .join("mkm_set_name_fixes.json");
-
-        match std::fs::read_to_string(&resource_path) {
-            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
-                eprintln!("Warning: Failed to parse mkm_set_name_fixes.json: {}", e);
-                HashMap::new()
-            }),
-            Err(e) => {
-                eprintln!("Warning: Failed to read mkm_set_name_fixes.json: {}", e);
-                HashMap::new()
-            }
-        }
+        load_resource("mkm_set_name_fixes.json", HashMap::new)
     }
 }
 
@@ -10,8 +10,9 @@ pub enum ProviderError {
     ParseError(String),
     #[error("Authentication error: {0}")]
     AuthError(String),
+    /// Carries how long the server asked us to wait, when it said
     #[error("Rate limit exceeded")]
-    RateLimitError,
+    RateLimitError(Option<std::time::Duration>),
     #[error("Configuration error: {0}")]
     ConfigurationError(String),
     #[error("Processing error: {0}")]
@@ -22,6 +23,7 @@ pub enum ProviderError {
 pub type ProviderResult<T> = Result<T, ProviderError>;
 
 // Core provider modules
+pub mod aws;
 pub mod provider_base;
 pub mod third_party;
 
@@ -33,7 +35,12 @@ pub mod mtgwiki;
 pub mod scryfall;
 
 // Re-export main provider types and implementations from third_party
-pub use provider_base::{AbstractProvider, BaseProvider, RateLimiter};
+pub use aws::{dry_run_s3_upload, upload_to_s3};
+pub use provider_base::{
+    is_not_found_error, response_cache, set_response_cache, set_response_dump_dir,
+    set_retry_config, AbstractProvider, BaseProvider, RateLimiter, ResponseCache, RetryConfig,
+    DEFAULT_CACHE_TTL_SECS,
+};
 pub use third_party::cardhoarder::CardHoarderProvider;
 pub use third_party::cardkingdom::CardKingdomProvider;
 pub use third_party::gatherer::GathererProvider;
@@ -62,7 +69,7 @@ impl From<ProviderError> for pyo3::PyErr {
             ProviderError::NetworkError(msg) => pyo3::exceptions::PyConnectionError::new_err(msg),
             ProviderError::ParseError(msg) => pyo3::exceptions::PyValueError::new_err(msg),
             ProviderError::AuthError(msg) => pyo3::exceptions::PyPermissionError::new_err(msg),
-            ProviderError::RateLimitError => {
+            ProviderError::RateLimitError(_) => {
                 pyo3::exceptions::PyRuntimeError::new_err("Rate limit exceeded")
             }
             ProviderError::ConfigurationError(msg) => {
@@ -99,3 +106,59 @@ pub fn add_provider_classes_to_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    /// Every provider that can be built offline. GathererProvider downloads
+    /// its mapping on construction, and the `github` module's sources are
+    /// missing, so neither is covered.
+    fn all_providers() -> Vec<Box<dyn AbstractProvider>> {
+        pyo3::prepare_freethreaded_python();
+        vec![
+            Box::new(CardHoarderProvider::new().unwrap()),
+            Box::new(CardKingdomProvider::new().unwrap()),
+            Box::new(MTGBanProvider::new().unwrap()),
+            Box::new(MultiverseBridgeProvider::new().unwrap()),
+            Box::new(TCGPlayerProvider::new().unwrap()),
+            Box::new(WhatsInStandardProvider::new().unwrap()),
+            Box::new(WizardsProvider::new().unwrap()),
+            Box::new(CardMarketProvider::new(None, Some(false)).unwrap()),
+            Box::new(EdhrecProviderCardRanks::new().unwrap()),
+            Box::new(MtgWikiProviderSecretLair::new().unwrap()),
+            Box::new(ScryfallProvider::new().unwrap()),
+            Box::new(ScryfallProviderOrientationDetector::new().unwrap()),
+        ]
+    }
+
+    #[test]
+    fn test_providers_conform_to_abstract_provider() {
+        let date_regex = regex::Regex::new(r"^\d{4}-\d{2}-\d{2}$").unwrap();
+        let mut class_ids = HashSet::new();
+
+        for provider in all_providers() {
+            let class_id = provider.get_class_id().to_string();
+            assert!(!class_id.is_empty(), "{} has no class_id", provider.get_class_name());
+            assert!(!provider.get_class_name().is_empty());
+            assert!(
+                class_ids.insert(class_id.clone()),
+                "class_id {} is used by more than one provider",
+                class_id
+            );
+
+            for (name, value) in provider.build_http_header() {
+                assert!(!name.is_empty(), "{} has an unnamed header", class_id);
+                assert!(!value.is_empty(), "{} sends an empty {} header", class_id, name);
+            }
+
+            assert!(
+                date_regex.is_match(&provider.today_date()),
+                "{} has a malformed today_date {}",
+                class_id,
+                provider.today_date()
+            );
+        }
+    }
+}
@@ -4,7 +4,228 @@ use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use reqwest::{Client, Response};
 use serde_json::Value;
+use sha2::{Digest, Sha256};
 use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use std::time::Duration;
+
+/// Retries attempted for a failed provider request by default
+pub const DEFAULT_MAX_RETRIES: u32 = 3;
+
+/// Backoff before the first retry by default, doubled for each retry after
+pub const DEFAULT_RETRY_BASE_MS: u64 = 500;
+
+/// How failed provider requests are retried
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryConfig {
+    pub max_retries: u32,
+    pub base_ms: u64,
+}
+
+impl RetryConfig {
+    /// Delay before retry number `attempt` (starting at 0)
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        Duration::from_millis(self.base_ms.saturating_mul(1 << attempt.min(16)))
+    }
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: DEFAULT_MAX_RETRIES,
+            base_ms: DEFAULT_RETRY_BASE_MS,
+        }
+    }
+}
+
+/// Retry settings picked up by every provider created afterwards
+static RETRY_CONFIG: RwLock<RetryConfig> = RwLock::new(RetryConfig {
+    max_retries: DEFAULT_MAX_RETRIES,
+    base_ms: DEFAULT_RETRY_BASE_MS,
+});
+
+/// Configure how providers retry failed requests
+pub fn set_retry_config(retry_config: RetryConfig) {
+    if let Ok(mut current) = RETRY_CONFIG.write() {
+        *current = retry_config;
+    }
+}
+
+/// Currently configured retry settings
+pub fn retry_config() -> RetryConfig {
+    RETRY_CONFIG
+        .read()
+        .map(|retry_config| *retry_config)
+        .unwrap_or_default()
+}
+
+/// Whether a failed request is worth retrying. Client errors (other than
+/// rate limiting) and unparseable responses will not improve on retry.
+pub fn is_retryable_error(error: &ProviderError) -> bool {
+    match error {
+        ProviderError::RateLimitError(_) => true,
+        ProviderError::NetworkError(msg) => !msg.starts_with("HTTP error 4"),
+        _ => false,
+    }
+}
+
+/// How long a `Retry-After` header asks us to wait. Only the delay-seconds
+/// form is understood.
+pub fn parse_retry_after(value: &str) -> Option<Duration> {
+    value.trim().parse::<u64>().ok().map(Duration::from_secs)
+}
+
+/// Whether a request failed because the resource does not exist
+pub fn is_not_found_error(error: &ProviderError) -> bool {
+    matches!(error, ProviderError::NetworkError(msg) if msg.starts_with("HTTP error 404"))
+}
+
+/// Run `operation` until it succeeds, retrying retryable failures up to
+/// `retry_config.max_retries` times with exponential backoff. A rate limited
+/// request instead waits as long as the server asked, when it said.
+pub async fn with_retries<T, F, Fut>(
+    retry_config: RetryConfig,
+    mut operation: F,
+) -> ProviderResult<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = ProviderResult<T>>,
+{
+    let mut attempt = 0;
+    loop {
+        match operation().await {
+            Err(e) if attempt < retry_config.max_retries && is_retryable_error(&e) => {
+                let delay = match e {
+                    ProviderError::RateLimitError(Some(retry_after)) => retry_after,
+                    _ => retry_config.backoff(attempt),
+                };
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// How long cached Scryfall responses are reused by default
+pub const DEFAULT_CACHE_TTL_SECS: u64 = 3600;
+
+/// On-disk cache of JSON responses, one `<sha256(url)>.json` file per URL
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResponseCache {
+    pub cache_dir: PathBuf,
+    pub ttl: Duration,
+}
+
+impl ResponseCache {
+    /// Cache under the system temp directory, reusing responses for `ttl`
+    pub fn with_ttl(ttl: Duration) -> Self {
+        Self {
+            cache_dir: std::env::temp_dir().join("mtgjson_cache"),
+            ttl,
+        }
+    }
+
+    /// File the response for `url` is cached in
+    pub fn cache_path(&self, url: &str) -> PathBuf {
+        let url_hash: String = Sha256::digest(url.as_bytes())
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+        self.cache_dir.join(format!("{}.json", url_hash))
+    }
+
+    /// The cached response for `url`, unless missing, unreadable or older
+    /// than the TTL
+    pub fn get(&self, url: &str) -> Option<Value> {
+        let cache_path = self.cache_path(url);
+        let age = std::fs::metadata(&cache_path)
+            .and_then(|metadata| metadata.modified())
+            .ok()?
+            .elapsed()
+            .unwrap_or_default();
+        if age > self.ttl {
+            return None;
+        }
+        let content = std::fs::read_to_string(&cache_path).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// Cache `response` for `url`. Scryfall error objects are never cached.
+    pub fn put(&self, url: &str, response: &Value) {
+        if response.get("object").and_then(|v| v.as_str()) == Some("error") {
+            return;
+        }
+        let written = std::fs::create_dir_all(&self.cache_dir)
+            .and_then(|_| std::fs::write(self.cache_path(url), response.to_string()));
+        if let Err(e) = written {
+            eprintln!("Warning: Failed to cache response for {}: {}", url, e);
+        }
+    }
+}
+
+static RESPONSE_CACHE: RwLock<Option<ResponseCache>> = RwLock::new(None);
+
+/// Enable (or with `None`, disable) response caching for providers created
+/// afterwards
+pub fn set_response_cache(response_cache: Option<ResponseCache>) {
+    if let Ok(mut current) = RESPONSE_CACHE.write() {
+        *current = response_cache;
+    }
+}
+
+/// Currently configured response cache, if caching is enabled
+pub fn response_cache() -> Option<ResponseCache> {
+    RESPONSE_CACHE
+        .read()
+        .map(|response_cache| response_cache.clone())
+        .unwrap_or_default()
+}
+
+/// Directory raw provider responses are dumped to, when enabled
+static RESPONSE_DUMP_DIR: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+/// Enable (or with `None`, disable) dumping of raw provider responses
+pub fn set_response_dump_dir(dump_dir: Option<PathBuf>) {
+    if let Ok(mut current) = RESPONSE_DUMP_DIR.write() {
+        *current = dump_dir;
+    }
+}
+
+/// Write a raw response body to `dump_dir`, named by provider and URL hash
+pub fn dump_response_to(
+    dump_dir: &Path,
+    class_id: &str,
+    url: &str,
+    body: &str,
+) -> std::io::Result<PathBuf> {
+    let url_hash: String = Sha256::digest(url.as_bytes())
+        .iter()
+        .take(8)
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+
+    std::fs::create_dir_all(dump_dir)?;
+    let dump_path = dump_dir.join(format!("{}_{}.txt", class_id, url_hash));
+    std::fs::write(&dump_path, body)?;
+    Ok(dump_path)
+}
+
+/// Dump a raw response body if response dumping is enabled
+fn dump_response(class_id: &str, url: &str, body: &str) {
+    let dump_dir = match RESPONSE_DUMP_DIR.read() {
+        Ok(dump_dir) => dump_dir.clone(),
+        Err(_) => None,
+    };
+
+    if let Some(dump_dir) = dump_dir {
+        if let Err(e) = dump_response_to(&dump_dir, class_id, url, body) {
+            eprintln!("Warning: Failed to dump response for {}: {}", url, e);
+        }
+    }
+}
 
 /// Abstract provider trait that all providers must implement
 #[async_trait]
@@ -76,6 +297,7 @@ pub struct BaseProvider {
     pub class_id: String,
     pub client: Client,
     pub headers: HashMap<String, String>,
+    pub retry_config: RetryConfig,
 }
 
 impl BaseProvider {
@@ -104,6 +326,7 @@ impl BaseProvider {
             class_id,
             client,
             headers,
+            retry_config: retry_config(),
         }
     }
 
@@ -134,14 +357,22 @@ impl BaseProvider {
             .map_err(|e| ProviderError::NetworkError(format!("Request failed: {}", e)))
     }
 
-    /// Download JSON content
-    pub async fn download_json(
+    /// Download a response body once, without retrying
+    async fn download_body_once(
         &self,
         url: &str,
         params: Option<HashMap<String, String>>,
-    ) -> ProviderResult<Value> {
+    ) -> ProviderResult<String> {
         let response = self.get_request(url, params).await?;
 
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(parse_retry_after);
+            return Err(ProviderError::RateLimitError(retry_after));
+        }
         if !response.status().is_success() {
             return Err(ProviderError::NetworkError(format!(
                 "HTTP error {}: {}",
@@ -151,8 +382,35 @@ impl BaseProvider {
         }
 
         response
-            .json()
+            .text()
             .await
+            .map_err(|e| ProviderError::NetworkError(format!("Text download error: {}", e)))
+    }
+
+    /// Download a response body, retrying failures per `retry_config`
+    async fn download_body(
+        &self,
+        url: &str,
+        params: Option<HashMap<String, String>>,
+    ) -> ProviderResult<String> {
+        let body = with_retries(self.retry_config, || {
+            self.download_body_once(url, params.clone())
+        })
+        .await?;
+        dump_response(&self.class_id, url, &body);
+
+        Ok(body)
+    }
+
+    /// Download JSON content
+    pub async fn download_json(
+        &self,
+        url: &str,
+        params: Option<HashMap<String, String>>,
+    ) -> ProviderResult<Value> {
+        let body = self.download_body(url, params).await?;
+
+        serde_json::from_str(&body)
             .map_err(|e| ProviderError::ParseError(format!("JSON parse error: {}", e)))
     }
 
@@ -162,20 +420,7 @@ impl BaseProvider {
         url: &str,
         params: Option<HashMap<String, String>>,
     ) -> ProviderResult<String> {
-        let response = self.get_request(url, params).await?;
-
-        if !response.status().is_success() {
-            return Err(ProviderError::NetworkError(format!(
-                "HTTP error {}: {}",
-                response.status(),
-                response.text().await.unwrap_or_default()
-            )));
-        }
-
-        response
-            .text()
-            .await
-            .map_err(|e| ProviderError::NetworkError(format!("Text download error: {}", e)))
+        self.download_body(url, params).await
     }
 }
 
@@ -213,3 +458,146 @@ impl RateLimiter {
         *self.last_call.lock().await = Utc::now();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[test]
+    fn test_response_cache_honors_ttl_and_skips_errors() {
+        let cache_dir = std::env::temp_dir().join("mtgjson_response_cache_test");
+        let _ = std::fs::remove_dir_all(&cache_dir);
+        let response_cache = ResponseCache {
+            cache_dir: cache_dir.clone(),
+            ttl: Duration::from_secs(60),
+        };
+
+        let url = "https://api.scryfall.com/sets/dom";
+        assert_eq!(response_cache.get(url), None);
+        response_cache.put(url, &serde_json::json!({"object": "set", "code": "dom"}));
+        assert_eq!(
+            response_cache.get(url),
+            Some(serde_json::json!({"object": "set", "code": "dom"}))
+        );
+
+        let expired = ResponseCache {
+            ttl: Duration::ZERO,
+            ..response_cache.clone()
+        };
+        std::thread::sleep(Duration::from_millis(10));
+        assert_eq!(expired.get(url), None);
+
+        let missing = "https://api.scryfall.com/sets/nope";
+        response_cache.put(
+            missing,
+            &serde_json::json!({"object": "error", "status": 404}),
+        );
+        assert!(!response_cache.cache_path(missing).exists());
+
+        let _ = std::fs::remove_dir_all(&cache_dir);
+    }
+
+    /// Fails with a network error `failures` times, then succeeds
+    async fn flaky_request(calls: &AtomicU32, failures: u32) -> ProviderResult<&'static str> {
+        if calls.fetch_add(1, Ordering::SeqCst) < failures {
+            Err(ProviderError::NetworkError("connection reset".to_string()))
+        } else {
+            Ok("ok")
+        }
+    }
+
+    #[tokio::test]
+    async fn test_with_retries_honors_max_retries() {
+        let retry_config = RetryConfig {
+            max_retries: 4,
+            base_ms: 1,
+        };
+
+        let calls = AtomicU32::new(0);
+        let result = with_retries(retry_config, || flaky_request(&calls, 4)).await;
+        assert_eq!(result.unwrap(), "ok");
+        assert_eq!(calls.load(Ordering::SeqCst), 5);
+
+        let calls = AtomicU32::new(0);
+        let result = with_retries(retry_config, || flaky_request(&calls, 5)).await;
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 5);
+    }
+
+    #[tokio::test]
+    async fn test_with_retries_does_not_retry_client_errors() {
+        let calls = AtomicU32::new(0);
+        let result: ProviderResult<()> = with_retries(RetryConfig::default(), || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(ProviderError::NetworkError(
+                "HTTP error 404 Not Found: ".to_string(),
+            ))
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_with_retries_waits_for_retry_after() {
+        assert_eq!(parse_retry_after(" 2 "), Some(Duration::from_secs(2)));
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"), None);
+
+        let retry_config = RetryConfig {
+            max_retries: 1,
+            base_ms: 60_000,
+        };
+        let calls = AtomicU32::new(0);
+        let result = tokio::time::timeout(
+            Duration::from_secs(5),
+            with_retries(retry_config, || async {
+                if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                    Err(ProviderError::RateLimitError(Some(Duration::from_millis(
+                        10,
+                    ))))
+                } else {
+                    Ok("ok")
+                }
+            }),
+        )
+        .await;
+        assert_eq!(result.unwrap().unwrap(), "ok");
+    }
+
+    #[test]
+    fn test_retry_backoff_doubles() {
+        let retry_config = RetryConfig::default();
+        assert_eq!(retry_config.backoff(0), Duration::from_millis(500));
+        assert_eq!(retry_config.backoff(2), Duration::from_millis(2000));
+    }
+
+    #[test]
+    fn test_dump_response() {
+        let dump_dir = std::env::temp_dir().join("mtgjson_response_dump_test");
+        let _ = std::fs::remove_dir_all(&dump_dir);
+
+        // Off by default
+        dump_response("sf", "https://api.scryfall.com/sets", r#"{"data": []}"#);
+        assert!(!dump_dir.exists());
+
+        let dump_path = dump_response_to(
+            &dump_dir,
+            "sf",
+            "https://api.scryfall.com/sets",
+            r#"{"data": []}"#,
+        )
+        .unwrap();
+        assert!(dump_path
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .starts_with("sf_"));
+        assert_eq!(
+            std::fs::read_to_string(&dump_path).unwrap(),
+            r#"{"data": []}"#
+        );
+
+        let _ = std::fs::remove_dir_all(&dump_dir);
+    }
+}
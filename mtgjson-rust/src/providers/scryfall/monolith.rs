@@ -1,7 +1,9 @@
 use super::sf_utils;
+use crate::builders::runtime::block_on_shared;
 use crate::prices::MtgjsonPricesObject;
 use crate::providers::{
-    AbstractProvider, BaseProvider, ProviderError, ProviderResult, RateLimiter,
+    response_cache, AbstractProvider, BaseProvider, ProviderError, ProviderResult, RateLimiter,
+    ResponseCache,
 };
 use async_trait::async_trait;
 use pyo3::prelude::*;
@@ -10,13 +12,14 @@ use pyo3::types::PyList;
 use reqwest::Response;
 use serde_json::Value;
 use std::collections::{HashMap, HashSet};
-use tokio::time::{sleep, Duration};
 
 #[pyclass(name = "ScryfallProvider")]
 pub struct ScryfallProvider {
     base: BaseProvider,
     rate_limiter: RateLimiter,
     cards_without_limits: HashSet<String>,
+    /// Where downloads are cached, when caching is enabled
+    response_cache: Option<ResponseCache>,
 }
 
 impl ScryfallProvider {
@@ -35,6 +38,8 @@ impl ScryfallProvider {
         "https://api.scryfall.com/cards/search?q=is:alchemy%20and%20oracle:/conjure|draft|%27s%20spellbook/&include_extras=true";
     const SPELLBOOK_SEARCH_URL: &'static str =
         "https://api.scryfall.com/cards/search?q=spellbook:%22{}%22&include_extras=true";
+    /// Most pages followed for one search before giving up
+    const MAX_PAGES: usize = 500;
 }
 
 #[pymethods]
@@ -49,6 +54,7 @@ impl ScryfallProvider {
             base,
             rate_limiter,
             cards_without_limits: HashSet::new(),
+            response_cache: response_cache(),
         })
     }
 
@@ -59,9 +65,8 @@ impl ScryfallProvider {
         starting_url: &str,
         params: Option<HashMap<String, String>>,
     ) -> PyResult<Bound<'py, PyList>> {
-        let runtime = tokio::runtime::Runtime::new()?;
-        let cards = runtime
-            .block_on(async { self.download_all_pages_async(starting_url, params).await })?;
+        let cards =
+            block_on_shared(async { self.download_all_pages_async(starting_url, params).await })?;
 
         let py_list = PyList::new(py, cards.iter().map(|v| v.to_string()).collect::<Vec<_>>());
         Ok(py_list.into_py(py))
@@ -73,18 +78,16 @@ impl ScryfallProvider {
         py: Python<'py>,
         set_code: &str,
     ) -> PyResult<Bound<'py, PyList>> {
-        let url = Self::CARDS_URL_ALL_DETAIL_BY_SET_CODE.replace("{}", set_code);
-        let cards = self.download_all_pages(py, &url, None)?;
-
-        // Sort by card name and collector number (Python compatibility)
-        // Note: This is a simplified sort - the real implementation would need to sort JSON objects
-        Ok(cards)
+        let cards = block_on_shared(self.download_cards_async(set_code))?;
+        Ok(PyList::new_bound(
+            py,
+            cards.iter().map(|v| v.to_string()).collect::<Vec<_>>(),
+        ))
     }
 
     /// Generate cards without limits
     pub fn generate_cards_without_limits(&mut self) -> PyResult<Vec<String>> {
-        let runtime = tokio::runtime::Runtime::new()?;
-        let cards = runtime.block_on(async {
+        let cards = block_on_shared(async {
             self.get_card_names(Self::CARDS_WITHOUT_LIMITS_URL)
                 .await
                 .map_err(|e| {
@@ -101,8 +104,7 @@ impl ScryfallProvider {
 
     /// Get alchemy cards with spellbooks
     pub fn get_alchemy_cards_with_spellbooks(&self) -> PyResult<Vec<String>> {
-        let runtime = tokio::runtime::Runtime::new()?;
-        runtime.block_on(async {
+        block_on_shared(async {
             self.get_card_names(Self::CARDS_WITH_ALCHEMY_SPELLBOOK_URL)
                 .await
                 .map_err(|e| {
@@ -116,9 +118,8 @@ impl ScryfallProvider {
 
     /// Get card names in spellbook
     pub fn get_card_names_in_spellbook(&self, card_name: &str) -> PyResult<Vec<String>> {
-        let runtime = tokio::runtime::Runtime::new()?;
         let url = Self::SPELLBOOK_SEARCH_URL.replace("{}", card_name);
-        runtime.block_on(async {
+        block_on_shared(async {
             self.get_card_names(&url).await.map_err(|e| {
                 PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
                     "Spellbook cards error: {}",
@@ -130,84 +131,80 @@ impl ScryfallProvider {
 
     /// Get catalog entry
     pub fn get_catalog_entry(&self, catalog_key: &str) -> PyResult<Vec<String>> {
-        let runtime = tokio::runtime::Runtime::new()?;
-        runtime
-            .block_on(async {
-                let url = Self::TYPE_CATALOG.replace("{0}", catalog_key);
-                match self.download(&url, None).await {
-                    Ok(data) => {
-                        if data.get("object").and_then(|v| v.as_str()) == Some("error") {
-                            return Err(ProviderError::NetworkError(format!(
-                                "Unable to build {}. Not found",
-                                catalog_key
-                            )));
-                        }
-
-                        let empty_vec = vec![];
-                        let catalog_data = data
-                            .get("data")
-                            .and_then(|v| v.as_array())
-                            .unwrap_or(&empty_vec);
-
-                        Ok(catalog_data
-                            .iter()
-                            .filter_map(|v| v.as_str())
-                            .map(|s| s.to_string())
-                            .collect())
+        block_on_shared(async {
+            let url = Self::TYPE_CATALOG.replace("{0}", catalog_key);
+            match self.download(&url, None).await {
+                Ok(data) => {
+                    if data.get("object").and_then(|v| v.as_str()) == Some("error") {
+                        return Err(ProviderError::NetworkError(format!(
+                            "Unable to build {}. Not found",
+                            catalog_key
+                        )));
                     }
-                    Err(e) => Err(e),
+
+                    let empty_vec = vec![];
+                    let catalog_data = data
+                        .get("data")
+                        .and_then(|v| v.as_array())
+                        .unwrap_or(&empty_vec);
+
+                    Ok(catalog_data
+                        .iter()
+                        .filter_map(|v| v.as_str())
+                        .map(|s| s.to_string())
+                        .collect())
                 }
-            })
-            .map_err(|e| {
-                PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Catalog error: {}", e))
-            })
+                Err(e) => Err(e),
+            }
+        })
+        .map_err(|e| {
+            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Catalog error: {}", e))
+        })
     }
 
     /// Get all Scryfall sets
     pub fn get_all_scryfall_sets(&self) -> PyResult<Vec<String>> {
-        let runtime = tokio::runtime::Runtime::new()?;
-        runtime
-            .block_on(async {
-                match self.download(Self::ALL_SETS_URL, None).await {
-                    Ok(data) => {
-                        if data.get("object").and_then(|v| v.as_str()) == Some("error") {
-                            return Err(ProviderError::NetworkError(
-                                "Downloading Scryfall data failed".to_string(),
-                            ));
-                        }
+        block_on_shared(async {
+            match self.download(Self::ALL_SETS_URL, None).await {
+                Ok(data) => {
+                    if data.get("object").and_then(|v| v.as_str()) == Some("error") {
+                        return Err(ProviderError::NetworkError(
+                            "Downloading Scryfall data failed".to_string(),
+                        ));
+                    }
 
-                        let empty_vec = vec![];
-                        let sets_data = data
-                            .get("data")
-                            .and_then(|v| v.as_array())
-                            .unwrap_or(&empty_vec);
-
-                        let mut set_codes: Vec<String> = sets_data
-                            .iter()
-                            .filter_map(|set_obj| {
-                                set_obj
-                                    .get("code")
-                                    .and_then(|v| v.as_str())
-                                    .map(|s| s.to_uppercase())
-                            })
-                            .collect();
+                    let empty_vec = vec![];
+                    let sets_data = data
+                        .get("data")
+                        .and_then(|v| v.as_array())
+                        .unwrap_or(&empty_vec);
+
+                    let mut set_codes: Vec<String> = sets_data
+                        .iter()
+                        .filter_map(|set_obj| {
+                            set_obj
+                                .get("code")
+                                .and_then(|v| v.as_str())
+                                .map(|s| s.to_uppercase())
+                        })
+                        .collect();
 
-                        // Remove Scryfall token sets (but leave extra sets)
-                        let set_codes_clone = set_codes.clone();
-                        set_codes.retain(|set_code| {
-                            !(set_code.starts_with('T')
-                                && set_codes_clone.contains(&set_code[1..].to_string()))
-                        });
+                    // Remove Scryfall token sets (but leave extra sets)
+                    let set_codes_clone = set_codes.clone();
+                    set_codes.retain(|set_code| {
+                        !(set_code.starts_with('T')
+                            && set_codes_clone.contains(&set_code[1..].to_string()))
+                    });
 
-                        set_codes.sort();
-                        Ok(set_codes)
-                    }
-                    Err(e) => Err(e),
+                    set_codes.sort();
+                    Ok(set_codes)
                 }
-            })
-            .map_err(|e| {
-                PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Sets error: {}", e))
-            })
+                Err(e) => Err(e),
+            }
+        })
+        .map_err(|e| {
+            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Sets error: {}", e))
+        })
     }
 
     /// Get sets already built (static method)
@@ -225,8 +222,7 @@ impl ScryfallProvider {
         skip_sets: Vec<String>,
         resume_build: bool,
     ) -> PyResult<Vec<String>> {
-        let runtime = tokio::runtime::Runtime::new()?;
-        let result = runtime.block_on(async {
+        let result = block_on_shared(async {
             let mut final_skip_sets = skip_sets;
 
             if resume_build {
@@ -270,19 +266,37 @@ impl ScryfallProvider {
 }
 
 impl ScryfallProvider {
+    /// Download every card of a set, extras and variations included
+    pub async fn download_cards_async(&self, set_code: &str) -> ProviderResult<Vec<Value>> {
+        let url = Self::CARDS_URL_ALL_DETAIL_BY_SET_CODE.replace("{}", set_code);
+        self.download_all_pages_async(&url, None).await
+    }
+
     /// Download all pages from a paginated endpoint
     async fn download_all_pages_async(
         &self,
         starting_url: &str,
         params: Option<HashMap<String, String>>,
+    ) -> ProviderResult<Vec<Value>> {
+        self.download_pages(starting_url, params, Self::MAX_PAGES)
+            .await
+    }
+
+    /// Download up to `max_pages` pages, following each page's `next_page`.
+    /// Stops with a warning if Scryfall hands back a page already downloaded.
+    async fn download_pages(
+        &self,
+        starting_url: &str,
+        params: Option<HashMap<String, String>>,
+        max_pages: usize,
     ) -> ProviderResult<Vec<Value>> {
         let mut all_cards: Vec<Value> = Vec::new();
+        let mut visited_urls: HashSet<String> = HashSet::new();
         let mut page_downloaded = 1;
         let mut current_url = format!("{}&page={}", starting_url, page_downloaded);
 
         loop {
-            self.rate_limiter.wait_if_needed().await;
-
+            visited_urls.insert(current_url.clone());
             let response: Value = self.download(&current_url, params.clone()).await?;
 
             if response.get("object").and_then(|v| v.as_str()) == Some("error") {
@@ -310,8 +324,28 @@ impl ScryfallProvider {
                 break;
             }
 
+            if page_downloaded >= max_pages {
+                eprintln!(
+                    "Warning: Stopped downloading {} after {} pages",
+                    starting_url, max_pages
+                );
+                break;
+            }
+
             page_downloaded += 1;
-            current_url = format!("{}&page={}", starting_url, page_downloaded);
+            let next_url = response
+                .get("next_page")
+                .and_then(|v| v.as_str())
+                .map(|url| url.to_string())
+                .unwrap_or_else(|| format!("{}&page={}", starting_url, page_downloaded));
+            if visited_urls.contains(&next_url) {
+                eprintln!(
+                    "Warning: Scryfall repeated page {} while downloading {}, stopping",
+                    next_url, starting_url
+                );
+                break;
+            }
+            current_url = next_url;
         }
 
         Ok(all_cards)
@@ -360,23 +394,31 @@ impl AbstractProvider for ScryfallProvider {
         url: &str,
         params: Option<HashMap<String, String>>,
     ) -> ProviderResult<Value> {
-        self.rate_limiter.wait_if_needed().await;
-
-        // Retry logic for chunked encoding errors
-        for retry in 0..3 {
-            match self.base.download_json(url, params.clone()).await {
-                Ok(result) => return Ok(result),
-                Err(ProviderError::NetworkError(msg)) if msg.contains("chunked") && retry < 2 => {
-                    sleep(Duration::from_secs(3 - retry as u64)).await;
-                    continue;
-                }
-                Err(e) => return Err(e),
+        let cache_key = match &params {
+            Some(params) => {
+                let mut params: Vec<_> = params.iter().collect();
+                params.sort();
+                format!("{}?{:?}", url, params)
             }
+            None => url.to_string(),
+        };
+        if let Some(cached) = self
+            .response_cache
+            .as_ref()
+            .and_then(|response_cache| response_cache.get(&cache_key))
+        {
+            return Ok(cached);
         }
 
-        Err(ProviderError::NetworkError(
-            "Max retries exceeded".to_string(),
-        ))
+        self.rate_limiter.wait_if_needed().await;
+
+        // Network failures (including chunked encoding errors) are retried
+        // with backoff by the base provider
+        let response = self.base.download_json(url, params).await?;
+        if let Some(response_cache) = &self.response_cache {
+            response_cache.put(&cache_key, &response);
+        }
+        Ok(response)
     }
 
     async fn download_raw(
@@ -483,3 +525,98 @@ impl AbstractProvider for ScryfallProvider {
         today_dict
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+
+    /// Serve every request with a one-card page whose `next_page` is
+    /// produced by `next_page` from the requested path
+    fn serve_pages(next_page: fn(&str, &str) -> String) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let server_url = base_url.clone();
+        std::thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let mut request_line = String::new();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                reader.read_line(&mut request_line).unwrap();
+                let path = request_line.split_whitespace().nth(1).unwrap_or("/");
+                let body = serde_json::json!({
+                    "object": "list",
+                    "has_more": true,
+                    "next_page": next_page(&server_url, path),
+                    "data": [{"name": "Loop Card"}],
+                })
+                .to_string();
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+            }
+        });
+        base_url
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_download_pages_stops_on_repeated_next_page() {
+        let provider = ScryfallProvider::new().unwrap();
+
+        let base_url = serve_pages(|server_url, path| format!("{}{}", server_url, path));
+        let cards = provider
+            .download_pages(&format!("{}/cards/search?q=e%3Atst", base_url), None, 50)
+            .await
+            .unwrap();
+        assert_eq!(cards.len(), 1);
+
+        // A fresh next_page every time still ends at the page cap
+        let base_url = serve_pages(|server_url, path| format!("{}{}x", server_url, path));
+        let cards = provider
+            .download_pages(&format!("{}/cards/search?q=e%3Atst", base_url), None, 3)
+            .await
+            .unwrap();
+        assert_eq!(cards.len(), 3);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_download_within_ttl_reads_from_cache() {
+        // Answers a single request; a second one means the cache was missed
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/sets/dom", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for (hits, mut stream) in listener.incoming().flatten().enumerate() {
+                assert_eq!(hits, 0, "Scryfall was hit twice for a cached URL");
+                let mut request_line = String::new();
+                BufReader::new(stream.try_clone().unwrap())
+                    .read_line(&mut request_line)
+                    .unwrap();
+                let body = serde_json::json!({"object": "set", "code": "dom"}).to_string();
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+            }
+        });
+
+        let cache_dir = std::env::temp_dir().join("mtgjson_scryfall_cache_test");
+        let _ = std::fs::remove_dir_all(&cache_dir);
+        let mut provider = ScryfallProvider::new().unwrap();
+        provider.response_cache = Some(ResponseCache {
+            cache_dir: cache_dir.clone(),
+            ttl: std::time::Duration::from_secs(60),
+        });
+
+        let first = provider.download(&url, None).await.unwrap();
+        let second = provider.download(&url, None).await.unwrap();
+        assert_eq!(first, second);
+        assert_eq!(second["code"], "dom");
+
+        let _ = std::fs::remove_dir_all(&cache_dir);
+    }
+}
@@ -2,6 +2,7 @@ use super::{
     super::{AbstractProvider, BaseProvider, ProviderError, ProviderResult},
     sf_utils,
 };
+use crate::builders::runtime::block_on_shared;
 use crate::prices::MtgjsonPricesObject;
 use async_trait::async_trait;
 use pyo3::prelude::*;
@@ -31,15 +32,14 @@ impl ScryfallProviderOrientationDetector {
 
     /// Get UUID to orientation mapping
     pub fn get_uuid_to_orientation_map(&self, set_code: &str) -> PyResult<HashMap<String, String>> {
-        let runtime = tokio::runtime::Runtime::new()?;
-        runtime
-            .block_on(async { self.get_uuid_to_orientation_map_async(set_code).await })
-            .map_err(|e| {
+        block_on_shared(async { self.get_uuid_to_orientation_map_async(set_code).await }).map_err(
+            |e| {
                 PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
                     "Orientation map error: {}",
                     e
                 ))
-            })
+            },
+        )
     }
 }
 
@@ -1,6 +1,19 @@
-use config::{Config, ConfigError, Environment, File};
+use config::{Config, ConfigError, Environment, File, FileFormat};
 use log::{info, warn};
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+/// Config file used instead of the default `mtgjson.properties`
+static PROVIDERS_CONFIG_PATH: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+/// Configure the file provider credentials are loaded from; `None` restores
+/// the default `mtgjson.properties`
+pub fn set_providers_config_path(config_path: Option<PathBuf>) {
+    if let Ok(mut current) = PROVIDERS_CONFIG_PATH.write() {
+        *current = config_path;
+    }
+}
 
 /// Configuration management for MTGJSON
 pub struct MtgjsonConfig {
@@ -10,8 +23,32 @@ pub struct MtgjsonConfig {
 impl MtgjsonConfig {
     /// Create a new configuration instance
     pub fn new() -> Result<Self, ConfigError> {
+        let config_path = PROVIDERS_CONFIG_PATH
+            .read()
+            .ok()
+            .and_then(|config_path| config_path.clone());
+        Self::from_path(config_path.as_deref())
+    }
+
+    /// Load `config_path`, which must exist, or the optional default
+    /// `mtgjson.properties` without one. `MTGJSON_` environment variables
+    /// override either. Files are read as INI unless their extension names
+    /// another format.
+    pub fn from_path(config_path: Option<&Path>) -> Result<Self, ConfigError> {
+        let config_file = match config_path {
+            Some(config_path) => {
+                let format = match config_path.extension().and_then(|ext| ext.to_str()) {
+                    Some("toml") => FileFormat::Toml,
+                    Some("json") => FileFormat::Json,
+                    Some("yaml" | "yml") => FileFormat::Yaml,
+                    _ => FileFormat::Ini,
+                };
+                File::new(&config_path.to_string_lossy(), format).required(true)
+            }
+            None => File::with_name("mtgjson.properties").required(false),
+        };
         let config = Config::builder()
-            .add_source(File::with_name("mtgjson.properties").required(false))
+            .add_source(config_file)
             .add_source(Environment::with_prefix("MTGJSON"))
             .build()?;
 
@@ -122,6 +159,29 @@ mod tests {
         assert!(headers.is_empty());
     }
 
+    #[test]
+    fn test_custom_config_path_is_honored() {
+        let config_dir = env::temp_dir().join("mtgjson_providers_config_test");
+        std::fs::create_dir_all(&config_dir).unwrap();
+        let config_path = config_dir.join("staging.properties");
+        std::fs::write(&config_path, "[Scryfall]\nclient_secret = staging_secret\n").unwrap();
+
+        let config = MtgjsonConfig::from_path(Some(&config_path)).unwrap();
+        assert!(config.has_section("Scryfall"));
+        assert_eq!(
+            config.get_string("Scryfall", "client_secret").unwrap(),
+            "staging_secret"
+        );
+
+        let default_config = MtgjsonConfig::from_path(None).unwrap();
+        assert!(!default_config.has_option("Scryfall", "client_secret"));
+
+        // A path given explicitly has to exist
+        assert!(MtgjsonConfig::from_path(Some(&config_dir.join("missing.properties"))).is_err());
+
+        let _ = std::fs::remove_dir_all(&config_dir);
+    }
+
     #[test]
     fn test_mtgjson_config_has_section() {
         // This would require actual config file testing
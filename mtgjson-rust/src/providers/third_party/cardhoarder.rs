@@ -1,3 +1,4 @@
+use crate::builders::runtime::block_on_shared;
 use crate::prices::MtgjsonPricesObject;
 use crate::providers::{AbstractProvider, BaseProvider, ProviderResult};
 use async_trait::async_trait;
@@ -28,18 +29,13 @@ impl CardHoarderProvider {
         &self,
         all_printings_path: &str,
     ) -> PyResult<HashMap<String, MtgjsonPricesObject>> {
-        let runtime = tokio::runtime::Runtime::new()?;
-        runtime
-            .block_on(async {
-                self.generate_today_price_dict_async(all_printings_path)
-                    .await
-            })
-            .map_err(|e| {
-                PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
-                    "Price dict error: {}",
-                    e
-                ))
-            })
+        block_on_shared(async {
+            self.generate_today_price_dict_async(all_printings_path)
+                .await
+        })
+        .map_err(|e| {
+            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Price dict error: {}", e))
+        })
     }
 
     /// Convert CardHoarder data to MTGJSON format
@@ -48,18 +44,13 @@ impl CardHoarderProvider {
         url_to_parse: &str,
         mtgo_to_mtgjson_map: HashMap<String, HashSet<String>>,
     ) -> PyResult<HashMap<String, f64>> {
-        let runtime = tokio::runtime::Runtime::new()?;
-        runtime
-            .block_on(async {
-                self.convert_cardhoarder_to_mtgjson_async(url_to_parse, mtgo_to_mtgjson_map)
-                    .await
-            })
-            .map_err(|e| {
-                PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
-                    "Conversion error: {}",
-                    e
-                ))
-            })
+        block_on_shared(async {
+            self.convert_cardhoarder_to_mtgjson_async(url_to_parse, mtgo_to_mtgjson_map)
+                .await
+        })
+        .map_err(|e| {
+            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Conversion error: {}", e))
+        })
     }
 
     /// Get MTGO to MTGJSON mapping
@@ -1,11 +1,46 @@
+use crate::builders::resources::load_resource;
+use crate::builders::runtime::block_on_shared;
 use crate::classes::{MtgjsonPricesObject, MtgjsonSealedProductObject};
 use crate::providers::{AbstractProvider, BaseProvider, ProviderError, ProviderResult};
 use async_trait::async_trait;
+use indexmap::IndexMap;
 use pyo3::prelude::*;
 use regex::Regex;
 use reqwest::Response;
+use serde::Deserialize;
 use serde_json::Value;
 use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
+
+static SEALED_NAME_FIXES: OnceLock<IndexMap<String, String>> = OnceLock::new();
+static SEALED_NAME_MAPPING: OnceLock<CardKingdomSealedNameMapping> = OnceLock::new();
+
+/// MTGJSON -> CardKingdom naming differences for sealed products,
+/// from `cardkingdom_sealed_name_mapping.json`
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CardKingdomSealedNameMapping {
+    /// Set names that CardKingdom calls something else
+    #[serde(default)]
+    pub editions: HashMap<String, String>,
+    /// Whole product names that CardKingdom calls something else
+    #[serde(default)]
+    pub products: HashMap<String, String>,
+}
+
+/// Substring replacements that unify sealed product nomenclature
+fn load_sealed_name_fixes() -> &'static IndexMap<String, String> {
+    SEALED_NAME_FIXES.get_or_init(|| {
+        let mut fixes: IndexMap<String, String> =
+            load_resource("sealed_name_fixes.json", IndexMap::new);
+        fixes.retain(|old, _| !old.starts_with("COMMENT"));
+        fixes
+    })
+}
+
+fn load_sealed_name_mapping() -> &'static CardKingdomSealedNameMapping {
+    SEALED_NAME_MAPPING
+        .get_or_init(|| load_resource("cardkingdom_sealed_name_mapping.json", Default::default))
+}
 
 #[pyclass(name = "CardKingdomProvider")]
 pub struct CardKingdomProvider {
@@ -40,23 +75,35 @@ impl CardKingdomProvider {
         name.to_lowercase()
     }
 
+    /// Comparison key for a CardKingdom sealed product name
+    #[staticmethod]
+    pub fn card_kingdom_sealed_key(product_name: &str) -> String {
+        Self::sealed_key(product_name, load_sealed_name_fixes(), None)
+    }
+
+    /// Comparison key for an MTGJSON sealed product name, translated
+    /// into CardKingdom's naming
+    #[staticmethod]
+    pub fn mtgjson_sealed_key(product_name: &str) -> String {
+        Self::sealed_key(
+            product_name,
+            load_sealed_name_fixes(),
+            Some(load_sealed_name_mapping()),
+        )
+    }
+
     /// Generate today's price dictionary
     pub fn generate_today_price_dict(
         &self,
         all_printings_path: &str,
     ) -> PyResult<HashMap<String, MtgjsonPricesObject>> {
-        let runtime = tokio::runtime::Runtime::new()?;
-        runtime
-            .block_on(async {
-                self.generate_today_price_dict_async(all_printings_path)
-                    .await
-            })
-            .map_err(|e| {
-                PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
-                    "Price dict error: {}",
-                    e
-                ))
-            })
+        block_on_shared(async {
+            self.generate_today_price_dict_async(all_printings_path)
+                .await
+        })
+        .map_err(|e| {
+            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Price dict error: {}", e))
+        })
     }
 
     /// Update sealed URLs for sealed products
@@ -64,22 +111,84 @@ impl CardKingdomProvider {
         &self,
         mut sealed_products: Vec<MtgjsonSealedProductObject>,
     ) -> PyResult<Vec<MtgjsonSealedProductObject>> {
-        let runtime = tokio::runtime::Runtime::new()?;
-        runtime
-            .block_on(async {
-                self.update_sealed_urls_async(&mut sealed_products).await?;
-                Ok(sealed_products)
-            })
-            .map_err(|e: Box<dyn std::error::Error + Send + Sync>| {
-                PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
-                    "Sealed URLs error: {}",
-                    e
-                ))
-            })
+        block_on_shared(async {
+            self.update_sealed_urls_async(&mut sealed_products).await?;
+            Ok(sealed_products)
+        })
+        .map_err(|e: Box<dyn std::error::Error + Send + Sync>| {
+            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Sealed URLs error: {}", e))
+        })
     }
 }
 
 impl CardKingdomProvider {
+    /// Apply sealed name fixes, then (for MTGJSON names) the CardKingdom
+    /// product/edition mapping, then strip for comparison
+    pub fn sealed_key(
+        product_name: &str,
+        fixes: &IndexMap<String, String>,
+        mapping: Option<&CardKingdomSealedNameMapping>,
+    ) -> String {
+        let mut name = product_name.to_string();
+        for (old, new) in fixes {
+            name = name.replace(old.as_str(), new);
+        }
+        let mut name = name.trim().to_lowercase();
+
+        if let Some(mapping) = mapping {
+            if let Some(product_name) = mapping.products.get(&name) {
+                name = product_name.clone();
+            } else if let Some((edition, ck_edition)) = mapping
+                .editions
+                .iter()
+                .filter(|(edition, _)| name.starts_with(edition.as_str()))
+                .max_by_key(|(edition, _)| edition.len())
+            {
+                name = format!("{}{}", ck_edition, &name[edition.len()..]);
+            }
+        }
+
+        Self::strip_sealed_name(&name)
+    }
+
+    /// Give sealed products without a CardKingdom ID the ID of the
+    /// CardKingdom product whose name matches
+    pub fn match_sealed_products_by_name(
+        sealed_products: &mut [MtgjsonSealedProductObject],
+        remote_products: &[Value],
+        fixes: &IndexMap<String, String>,
+        mapping: &CardKingdomSealedNameMapping,
+    ) {
+        let ck_ids_by_key: HashMap<String, String> = remote_products
+            .iter()
+            .filter_map(|remote_product| {
+                let name = remote_product.get("name")?.as_str()?;
+                let id = match remote_product.get("id")? {
+                    Value::String(id) => id.clone(),
+                    Value::Number(id) => id.to_string(),
+                    _ => return None,
+                };
+                Some((Self::sealed_key(name, fixes, None), id))
+            })
+            .collect();
+
+        for product in sealed_products {
+            let identifiers = product
+                .identifiers
+                .get_or_insert_with(crate::classes::MtgjsonIdentifiers::new);
+            if identifiers.card_kingdom_id.is_some() {
+                continue;
+            }
+
+            if let Some(name) = &product.name {
+                let key = Self::sealed_key(name, fixes, Some(mapping));
+                if let Some(card_kingdom_id) = ck_ids_by_key.get(&key) {
+                    identifiers.card_kingdom_id = Some(card_kingdom_id.clone());
+                }
+            }
+        }
+    }
+
     /// Get today's date in YYYY-MM-DD format
     fn today_date(&self) -> String {
         chrono::Utc::now().format("%Y-%m-%d").to_string()
@@ -168,6 +277,13 @@ impl CardKingdomProvider {
             .and_then(|v| v.as_array())
             .unwrap_or(&empty_vec);
 
+        Self::match_sealed_products_by_name(
+            sealed_products,
+            data_array,
+            load_sealed_name_fixes(),
+            load_sealed_name_mapping(),
+        );
+
         for product in sealed_products {
             if let Some(ref identifiers) = product.identifiers {
                 if let Some(card_kingdom_id) = &identifiers.card_kingdom_id {
@@ -341,3 +457,64 @@ impl AbstractProvider for CardKingdomProvider {
         today_dict
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_sealed_products_by_name_uses_mapping() {
+        let mut fixes = IndexMap::new();
+        fixes.insert("Set of Two".to_string(), "Set of 2".to_string());
+
+        let mut mapping = CardKingdomSealedNameMapping::default();
+        mapping.products.insert(
+            "planechase game pack elemental thunder".to_string(),
+            "planechase 2009 elemental thunder deck".to_string(),
+        );
+        mapping
+            .editions
+            .insert("ninth edition".to_string(), "9th edition".to_string());
+
+        let remote_products = vec![
+            serde_json::json!({"id": 101, "name": "Planechase 2009 Elemental Thunder Deck"}),
+            serde_json::json!({"id": "102", "name": "9th Edition Booster Box"}),
+            serde_json::json!({"id": "103", "name": "Gift Box - Set of 2"}),
+        ];
+
+        let mut sealed_products: Vec<MtgjsonSealedProductObject> = [
+            "Planechase Game Pack Elemental Thunder",
+            "Ninth Edition Booster Box",
+            "Gift Box Set of Two",
+            "Ninth Edition Fat Pack",
+        ]
+        .iter()
+        .map(|name| {
+            let mut product = MtgjsonSealedProductObject::new();
+            product.name = Some(name.to_string());
+            product
+        })
+        .collect();
+
+        CardKingdomProvider::match_sealed_products_by_name(
+            &mut sealed_products,
+            &remote_products,
+            &fixes,
+            &mapping,
+        );
+
+        let ids: Vec<Option<String>> = sealed_products
+            .iter()
+            .map(|p| p.identifiers.as_ref().unwrap().card_kingdom_id.clone())
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                Some("101".to_string()),
+                Some("102".to_string()),
+                Some("103".to_string()),
+                None,
+            ]
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use crate::builders::resources::load_resource;
+use crate::builders::runtime::block_on_shared;
 use crate::classes::MtgjsonPricesObject;
 use crate::providers::{AbstractProvider, BaseProvider, ProviderResult};
 use async_trait::async_trait;
@@ -6,6 +8,28 @@ use pyo3::prelude::*;
 use reqwest::Response;
 use serde_json::Value;
 use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
+
+static GATHERER_SET_CODES: OnceLock<HashMap<String, String>> = OnceLock::new();
+
+/// Load MTGJSON set code -> Gatherer set code overrides from JSON resource file
+fn load_gatherer_set_codes() -> &'static HashMap<String, String> {
+    GATHERER_SET_CODES.get_or_init(|| load_resource("gatherer_set_codes.json", HashMap::new))
+}
+
+/// Gatherer's code for an MTGJSON set, if Gatherer uses a different one
+pub fn gatherer_set_code(mtgjson_code: &str) -> Option<String> {
+    gatherer_set_code_from(load_gatherer_set_codes(), mtgjson_code)
+}
+
+fn gatherer_set_code_from(
+    gatherer_set_codes: &HashMap<String, String>,
+    mtgjson_code: &str,
+) -> Option<String> {
+    gatherer_set_codes
+        .get(&mtgjson_code.to_uppercase())
+        .cloned()
+}
 
 #[pyclass(name = "GathererProvider")]
 pub struct GathererProvider {
@@ -47,17 +71,7 @@ impl GathererProvider {
         url: String,
         params: Option<HashMap<String, String>>,
     ) -> PyResult<PyObject> {
-        let rt = match tokio::runtime::Runtime::new() {
-            Ok(rt) => rt,
-            Err(e) => {
-                return Err(PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
-                    "Runtime creation error: {}",
-                    e
-                )));
-            }
-        };
-
-        let result = rt.block_on(async {
+        let result = block_on_shared(async {
             match self.base.download_json(&url, params).await {
                 Ok(json) => json,
                 Err(e) => {
@@ -83,6 +97,25 @@ impl GathererProvider {
         }
     }
 
+    /// Get card(s) matching a given multiverseId that Gatherer files under
+    /// the given MTGJSON set. Entries without a set code always match.
+    pub fn get_cards_for_set(
+        &self,
+        multiverse_id: String,
+        set_code: &str,
+    ) -> PyResult<Vec<HashMap<String, String>>> {
+        let target_code = gatherer_set_code(set_code).unwrap_or_else(|| set_code.to_uppercase());
+
+        Ok(self
+            .get_cards(multiverse_id)?
+            .into_iter()
+            .filter(|card| {
+                card.get("set_code")
+                    .map_or(true, |code| code.eq_ignore_ascii_case(&target_code))
+            })
+            .collect())
+    }
+
     /// Get the multiverse ID to data mapping
     #[getter]
     pub fn get_multiverse_id_to_data(
@@ -111,11 +144,7 @@ impl GathererProvider {
 
     /// Initialize the gatherer mapping data
     fn initialize_data(&mut self) -> PyResult<()> {
-        let rt = tokio::runtime::Runtime::new().map_err(|e| {
-            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Runtime error: {}", e))
-        })?;
-
-        let data = rt.block_on(async {
+        let data = block_on_shared(async {
             self.base
                 .download_json(Self::GATHERER_ID_MAPPING_URL, None)
                 .await
@@ -238,3 +267,25 @@ impl AbstractProvider for GathererProvider {
         HashMap::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gatherer_set_code_mapping() {
+        let mut gatherer_set_codes = HashMap::new();
+        gatherer_set_codes.insert("DD1".to_string(), "EVG".to_string());
+        gatherer_set_codes.insert("EVG".to_string(), "DD3_EVG".to_string());
+
+        assert_eq!(
+            gatherer_set_code_from(&gatherer_set_codes, "dd1"),
+            Some("EVG".to_string())
+        );
+        assert_eq!(
+            gatherer_set_code_from(&gatherer_set_codes, "EVG"),
+            Some("DD3_EVG".to_string())
+        );
+        assert_eq!(gatherer_set_code_from(&gatherer_set_codes, "DOM"), None);
+    }
+}
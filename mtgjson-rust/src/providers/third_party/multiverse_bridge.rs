@@ -1,10 +1,34 @@
+use crate::builders::runtime::block_on_shared;
 use crate::providers::{AbstractProvider, BaseProvider, ProviderError, ProviderResult};
 use async_trait::async_trait;
+use once_cell::sync::OnceCell;
 use pyo3::prelude::*;
 use reqwest::Response;
 use serde_json::Value;
 use std::collections::HashMap;
 
+/// MultiverseBridge's ids for MTGJSON cards and sets
+#[derive(Debug, Clone, Default)]
+pub struct RosettaStone {
+    /// Rosetta Stone rows for each Scryfall id, one per finish
+    pub cards: HashMap<String, Vec<Value>>,
+    /// Cardsphere set id for each MTGJSON set code
+    pub sets: HashMap<String, i32>,
+}
+
+/// Downloaded on first use. A failed download isn't kept, so the next set
+/// tries again.
+static ROSETTA_STONE: OnceCell<RosettaStone> = OnceCell::new();
+
+/// The Rosetta Stone, downloaded once per build
+pub fn rosetta_stone() -> ProviderResult<&'static RosettaStone> {
+    ROSETTA_STONE.get_or_try_init(|| {
+        let provider = MultiverseBridgeProvider::new()
+            .map_err(|e| ProviderError::ConfigurationError(e.to_string()))?;
+        block_on_shared(provider.download_rosetta_stone_async())
+    })
+}
+
 /// Multiverse Bridge Provider
 #[pyclass(name = "MultiverseBridgeProvider")]
 pub struct MultiverseBridgeProvider {
@@ -22,6 +46,46 @@ impl MultiverseBridgeProvider {
     }
 }
 
+impl MultiverseBridgeProvider {
+    const ROSETTA_STONE_SETS_URL: &'static str = "https://www.multiversebridge.com/api/v1/sets";
+    const ROSETTA_STONE_CARDS_URL: &'static str =
+        "https://cdn.multiversebridge.com/mtgjson_build.json";
+
+    /// Download the Rosetta Stone's card and set ids
+    pub async fn download_rosetta_stone_async(&self) -> ProviderResult<RosettaStone> {
+        let card_rows = self.download(Self::ROSETTA_STONE_CARDS_URL, None).await?;
+        let set_rows = self.download(Self::ROSETTA_STONE_SETS_URL, None).await?;
+        Ok(parse_rosetta_stone(&card_rows, &set_rows))
+    }
+}
+
+/// Index the Rosetta Stone's card rows by Scryfall id and its set rows by
+/// MTGJSON set code
+fn parse_rosetta_stone(card_rows: &Value, set_rows: &Value) -> RosettaStone {
+    let mut rosetta_stone = RosettaStone::default();
+
+    for card_row in card_rows.as_array().into_iter().flatten() {
+        if let Some(scryfall_id) = card_row.get("scryfall_id").and_then(|v| v.as_str()) {
+            rosetta_stone
+                .cards
+                .entry(scryfall_id.to_string())
+                .or_default()
+                .push(card_row.clone());
+        }
+    }
+    for set_row in set_rows.as_array().into_iter().flatten() {
+        let set_code = set_row.get("mtgjson_code").and_then(|v| v.as_str());
+        let cardsphere_id = set_row.get("cs_id").and_then(|v| v.as_i64());
+        if let (Some(set_code), Some(cardsphere_id)) = (set_code, cardsphere_id) {
+            rosetta_stone
+                .sets
+                .insert(set_code.to_uppercase(), cardsphere_id as i32);
+        }
+    }
+
+    rosetta_stone
+}
+
 #[async_trait]
 impl AbstractProvider for MultiverseBridgeProvider {
     fn get_class_id(&self) -> &str {
@@ -78,3 +142,24 @@ impl AbstractProvider for MultiverseBridgeProvider {
         HashMap::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_rosetta_stone_indexes_cards_and_sets() {
+        let card_rows = json!([
+            {"scryfall_id": "sol-ring", "cs_id": 101, "is_foil": false},
+            {"scryfall_id": "sol-ring", "cs_id": 102, "is_foil": true},
+            {"cs_id": 103}
+        ]);
+        let set_rows = json!([{"mtgjson_code": "c21", "cs_id": 7}, {"mtgjson_code": "XYZ"}]);
+
+        let rosetta_stone = parse_rosetta_stone(&card_rows, &set_rows);
+        assert_eq!(rosetta_stone.cards.len(), 1);
+        assert_eq!(rosetta_stone.cards["sol-ring"].len(), 2);
+        assert_eq!(rosetta_stone.sets, HashMap::from([("C21".to_string(), 7)]));
+    }
+}
@@ -1,27 +1,49 @@
-use crate::classes::{MtgjsonPricesObject, MtgjsonSealedProductObject};
-use crate::providers::{AbstractProvider, BaseProvider, ProviderError, ProviderResult};
+use crate::builders::runtime::block_on_shared;
+use crate::classes::{MtgjsonCardObject, MtgjsonPricesObject, MtgjsonSealedProductObject};
+use crate::compiled_classes::{MtgjsonTcgplayerSku, MtgjsonTcgplayerSkus};
+use crate::providers::scryfall::sf_utils::MtgjsonConfig;
+use crate::providers::{
+    AbstractProvider, BaseProvider, ProviderError, ProviderResult, RateLimiter,
+};
 use async_trait::async_trait;
 use pyo3::prelude::*;
 
 use reqwest::Response;
 use serde_json::Value;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Product ids resolved per SKU request
+const SKU_BATCH_SIZE: usize = 100;
+
+/// Exchanges TCGPlayer API keys for a bearer token
+const TOKEN_URL: &str = "https://api.tcgplayer.com/token";
+
+/// API version used unless `[TCGPlayer] api_version` names another
+const DEFAULT_API_VERSION: &str = "v1.39.0";
 
 #[pyclass(name = "TCGPlayerProvider")]
 pub struct TCGPlayerProvider {
     base: BaseProvider,
     api_version: String,
+    /// Sent with every request; `None` when no token could be obtained
+    bearer_token: Option<String>,
+    rate_limiter: RateLimiter,
+    /// Product id -> its SKUs, kept for the rest of the build
+    sku_cache: tokio::sync::Mutex<HashMap<String, Vec<Value>>>,
 }
 
 #[pymethods]
 impl TCGPlayerProvider {
     #[new]
     pub fn new() -> PyResult<Self> {
-        let headers = HashMap::new();
-        let base = BaseProvider::new("tcg".to_string(), headers);
-        let api_version = "v1.39.0".to_string();
+        let config = MtgjsonConfig::new().ok();
+        let api_version = config
+            .as_ref()
+            .and_then(|config| config.get_string("TCGPlayer", "api_version").ok())
+            .unwrap_or_else(|| DEFAULT_API_VERSION.to_string());
+        let bearer_token = config.as_ref().and_then(request_tcgplayer_bearer);
 
-        Ok(Self { base, api_version })
+        Ok(Self::with_bearer_token(bearer_token, api_version))
     }
 
     /// Generate today's price dictionary
@@ -29,18 +51,13 @@ impl TCGPlayerProvider {
         &self,
         all_printings_path: &str,
     ) -> PyResult<HashMap<String, MtgjsonPricesObject>> {
-        let runtime = tokio::runtime::Runtime::new()?;
-        runtime
-            .block_on(async {
-                self.generate_today_price_dict_async(all_printings_path)
-                    .await
-            })
-            .map_err(|e: ProviderError| {
-                PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
-                    "Price dict error: {}",
-                    e
-                ))
-            })
+        block_on_shared(async {
+            self.generate_today_price_dict_async(all_printings_path)
+                .await
+        })
+        .map_err(|e: ProviderError| {
+            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Price dict error: {}", e))
+        })
     }
 
     /// Update sealed URLs for sealed products
@@ -65,12 +82,19 @@ impl TCGPlayerProvider {
 
     /// Get TCGPlayer magic set IDs
     pub fn get_tcgplayer_magic_set_ids(&self) -> PyResult<Vec<(String, String)>> {
-        let runtime = tokio::runtime::Runtime::new()?;
-        runtime
-            .block_on(async { self.get_tcgplayer_magic_set_ids_async().await })
-            .map_err(|e| {
-                PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Set IDs error: {}", e))
-            })
+        block_on_shared(async { self.get_tcgplayer_magic_set_ids_async().await }).map_err(|e| {
+            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Set IDs error: {}", e))
+        })
+    }
+
+    /// Build TcgplayerSkus for a set's cards, keyed by MTGJSON UUID
+    pub fn build_tcgplayer_skus(
+        &self,
+        cards: Vec<MtgjsonCardObject>,
+    ) -> PyResult<MtgjsonTcgplayerSkus> {
+        block_on_shared(async { self.build_tcgplayer_skus_async(&cards).await }).map_err(|e| {
+            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("SKU data error: {}", e))
+        })
     }
 
     /// Get TCGPlayer SKU data
@@ -79,10 +103,9 @@ impl TCGPlayerProvider {
         py: Python,
         group_id_and_name: (String, String),
     ) -> PyResult<PyObject> {
-        let runtime = tokio::runtime::Runtime::new()?;
-        let result = runtime
-            .block_on(async { self.get_tcgplayer_sku_data_async(group_id_and_name).await })
-            .map_err(|e| {
+        let result =
+            block_on_shared(async { self.get_tcgplayer_sku_data_async(group_id_and_name).await })
+                .map_err(|e| {
                 PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("SKU data error: {}", e))
             })?;
 
@@ -104,6 +127,91 @@ impl TCGPlayerProvider {
 }
 
 impl TCGPlayerProvider {
+    /// Provider authorizing its requests with `bearer_token`
+    pub fn with_bearer_token(bearer_token: Option<String>, api_version: String) -> Self {
+        let mut headers = HashMap::new();
+        if let Some(bearer_token) = &bearer_token {
+            headers.insert(
+                "Authorization".to_string(),
+                format!("Bearer {}", bearer_token),
+            );
+        }
+
+        Self {
+            base: BaseProvider::new("tcg".to_string(), headers),
+            api_version,
+            bearer_token,
+            rate_limiter: RateLimiter::new(5.0),
+            sku_cache: tokio::sync::Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Build TcgplayerSkus for a set's cards, keyed by MTGJSON UUID. Fails
+    /// without a bearer token, as TCGPlayer answers none of the requests.
+    pub async fn build_tcgplayer_skus_async<'a>(
+        &self,
+        cards: impl IntoIterator<Item = &'a MtgjsonCardObject>,
+    ) -> ProviderResult<MtgjsonTcgplayerSkus> {
+        if self.bearer_token.is_none() {
+            return Err(ProviderError::AuthError(
+                "No TCGPlayer bearer token, unable to request SKUs".to_string(),
+            ));
+        }
+
+        let product_uuids = map_tcgplayer_products(cards);
+        let product_ids: Vec<String> = product_uuids.keys().cloned().collect();
+        let skus = self.get_product_skus_async(&product_ids).await?;
+
+        Ok(map_skus_to_uuids(&product_uuids, &skus))
+    }
+
+    /// SKUs of each product, looked up in batches. Results are cached so a
+    /// product is only requested once per build.
+    async fn get_product_skus_async(&self, product_ids: &[String]) -> ProviderResult<Vec<Value>> {
+        let mut sku_cache = self.sku_cache.lock().await;
+
+        let missing_ids: Vec<&str> = product_ids
+            .iter()
+            .filter(|product_id| !sku_cache.contains_key(*product_id))
+            .map(String::as_str)
+            .collect();
+        for batch in missing_ids.chunks(SKU_BATCH_SIZE) {
+            self.rate_limiter.wait_if_needed().await;
+
+            let url = format!(
+                "https://api.tcgplayer.com/{}/catalog/products/{}/skus",
+                self.api_version,
+                batch.join(",")
+            );
+            let response = self.download(&url, None).await?;
+
+            // Products without SKUs are cached too, so they aren't retried
+            for product_id in batch {
+                sku_cache.entry(product_id.to_string()).or_default();
+            }
+            for sku in response
+                .get("results")
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+            {
+                if let Some(product_id) = sku.get("productId").and_then(|v| v.as_u64()) {
+                    sku_cache
+                        .entry(product_id.to_string())
+                        .or_default()
+                        .push(sku.clone());
+                }
+            }
+        }
+
+        Ok(product_ids
+            .iter()
+            .filter_map(|product_id| sku_cache.get(product_id))
+            .flatten()
+            .cloned()
+            .collect())
+    }
+
     async fn generate_today_price_dict_async(
         &self,
         _all_printings_path: &str,
@@ -195,6 +303,145 @@ impl TCGPlayerProvider {
     }
 }
 
+/// Request a bearer token with the `[TCGPlayer]` keys in `config`. `None`,
+/// with a warning, when the keys are missing or TCGPlayer refuses them.
+fn request_tcgplayer_bearer(config: &MtgjsonConfig) -> Option<String> {
+    let key = |option: &str| {
+        config
+            .get_string("TCGPlayer", option)
+            .ok()
+            .filter(|value| !value.is_empty())
+    };
+    let (Some(client_id), Some(client_secret)) = (key("client_id"), key("client_secret")) else {
+        eprintln!("Warning: TCGPlayer keys not established. Skipping requests");
+        return None;
+    };
+
+    let form = [
+        ("grant_type", "client_credentials".to_string()),
+        ("client_id", client_id),
+        ("client_secret", client_secret),
+    ];
+    let token = block_on_shared(async move {
+        reqwest::Client::new()
+            .post(TOKEN_URL)
+            .form(&form)
+            .timeout(std::time::Duration::from_secs(60))
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<Value>()
+            .await
+    });
+
+    match token {
+        Ok(token) => token
+            .get("access_token")
+            .and_then(|v| v.as_str())
+            .filter(|access_token| !access_token.is_empty())
+            .map(str::to_string),
+        Err(e) => {
+            eprintln!("Warning: Unable to contact TCGPlayer: {}", e);
+            None
+        }
+    }
+}
+
+/// TCGPlayer condition names by `conditionId`
+fn condition_name(condition_id: u64) -> Option<&'static str> {
+    match condition_id {
+        1 => Some("NEAR MINT"),
+        2 => Some("LIGHTLY PLAYED"),
+        3 => Some("MODERATELY PLAYED"),
+        4 => Some("HEAVILY PLAYED"),
+        5 => Some("DAMAGED"),
+        6 => Some("UNOPENED"),
+        _ => None,
+    }
+}
+
+/// TCGPlayer printing names by `printingId`
+fn printing_name(printing_id: u64) -> Option<&'static str> {
+    match printing_id {
+        1 => Some("NON FOIL"),
+        2 => Some("FOIL"),
+        _ => None,
+    }
+}
+
+/// TCGPlayer language names by `languageId`
+fn language_name(language_id: u64) -> Option<&'static str> {
+    match language_id {
+        1 => Some("ENGLISH"),
+        2 => Some("CHINESE SIMPLIFIED"),
+        3 => Some("CHINESE TRADITIONAL"),
+        4 => Some("FRENCH"),
+        5 => Some("GERMAN"),
+        6 => Some("ITALIAN"),
+        7 => Some("JAPANESE"),
+        8 => Some("KOREAN"),
+        9 => Some("PORTUGUESE BRAZIL"),
+        10 => Some("RUSSIAN"),
+        11 => Some("SPANISH"),
+        _ => None,
+    }
+}
+
+/// Convert a TCGPlayer SKU into MTGJSON's record, naming its condition,
+/// language and printing. SKUs with ids we don't recognize are dropped.
+pub fn convert_sku(sku: &Value) -> Option<MtgjsonTcgplayerSku> {
+    let id = |key: &str| sku.get(key).and_then(|v| v.as_u64());
+
+    Some(MtgjsonTcgplayerSku {
+        sku_id: id("skuId")?,
+        product_id: id("productId")?,
+        condition: condition_name(id("conditionId")?)?.to_string(),
+        language: language_name(id("languageId")?)?.to_string(),
+        printing: printing_name(id("printingId")?)?.to_string(),
+    })
+}
+
+/// TCGPlayer product id -> UUIDs of the cards sold as it, covering both
+/// normal and etched products
+pub fn map_tcgplayer_products<'a>(
+    cards: impl IntoIterator<Item = &'a MtgjsonCardObject>,
+) -> BTreeMap<String, Vec<String>> {
+    let mut product_uuids: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for card in cards {
+        let product_ids = [
+            &card.identifiers.tcgplayer_product_id,
+            &card.identifiers.tcgplayer_etched_product_id,
+        ];
+        for product_id in product_ids.into_iter().flatten() {
+            if !product_id.is_empty() {
+                product_uuids
+                    .entry(product_id.clone())
+                    .or_default()
+                    .push(card.uuid.clone());
+            }
+        }
+    }
+    product_uuids
+}
+
+/// File each SKU under the UUIDs of the cards sharing its product
+pub fn map_skus_to_uuids(
+    product_uuids: &BTreeMap<String, Vec<String>>,
+    skus: &[Value],
+) -> MtgjsonTcgplayerSkus {
+    let mut tcgplayer_skus = MtgjsonTcgplayerSkus::default();
+    for sku in skus {
+        let Some(record) = convert_sku(sku) else {
+            continue;
+        };
+        let uuids = product_uuids.get(&record.product_id.to_string());
+        for uuid in uuids.into_iter().flatten() {
+            tcgplayer_skus.add_skus(uuid, [record.clone()]);
+        }
+    }
+    tcgplayer_skus
+}
+
 #[async_trait]
 impl AbstractProvider for TCGPlayerProvider {
     fn get_class_id(&self) -> &str {
@@ -252,3 +499,87 @@ impl AbstractProvider for TCGPlayerProvider {
         HashMap::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card(
+        uuid: &str,
+        product_id: Option<&str>,
+        etched_product_id: Option<&str>,
+    ) -> MtgjsonCardObject {
+        let mut card = MtgjsonCardObject::new(false);
+        card.uuid = uuid.to_string();
+        card.identifiers.tcgplayer_product_id = product_id.map(str::to_string);
+        card.identifiers.tcgplayer_etched_product_id = etched_product_id.map(str::to_string);
+        card
+    }
+
+    #[test]
+    fn test_skus_map_to_uuids() {
+        let cards = vec![
+            card("uuid-a", Some("1001"), None),
+            card("uuid-b", Some("1001"), Some("1002")),
+            card("uuid-c", Some(""), None),
+        ];
+        let product_uuids = map_tcgplayer_products(&cards);
+        assert_eq!(product_uuids["1001"], vec!["uuid-a", "uuid-b"]);
+        assert_eq!(product_uuids["1002"], vec!["uuid-b"]);
+        assert_eq!(product_uuids.len(), 2);
+
+        // Shaped like a /catalog/products/{productIds}/skus response
+        let response = serde_json::json!({
+            "success": true,
+            "results": [
+                {"skuId": 1, "productId": 1001, "languageId": 1, "printingId": 1, "conditionId": 1},
+                {"skuId": 2, "productId": 1001, "languageId": 7, "printingId": 2, "conditionId": 2},
+                {"skuId": 3, "productId": 1002, "languageId": 1, "printingId": 2, "conditionId": 1},
+                {"skuId": 4, "productId": 9999, "languageId": 1, "printingId": 1, "conditionId": 1}
+            ]
+        });
+        let skus = response["results"].as_array().unwrap();
+
+        let tcgplayer_skus = map_skus_to_uuids(&product_uuids, skus);
+
+        assert_eq!(tcgplayer_skus.tcgplayer_skus.len(), 2);
+        let sku_ids = |uuid: &str| -> Vec<u64> {
+            tcgplayer_skus.tcgplayer_skus[uuid]
+                .iter()
+                .map(|sku| sku.sku_id)
+                .collect()
+        };
+        assert_eq!(sku_ids("uuid-a"), vec![1, 2]);
+        assert_eq!(sku_ids("uuid-b"), vec![1, 2, 3]);
+        assert_eq!(
+            serde_json::to_value(&tcgplayer_skus.tcgplayer_skus["uuid-a"][1]).unwrap(),
+            serde_json::json!({
+                "skuId": 2,
+                "productId": 1001,
+                "condition": "LIGHTLY PLAYED",
+                "language": "JAPANESE",
+                "printing": "FOIL"
+            })
+        );
+    }
+
+    #[test]
+    fn test_requests_carry_the_bearer_token() {
+        let provider =
+            TCGPlayerProvider::with_bearer_token(Some("abc".to_string()), "v1.39.0".to_string());
+        assert_eq!(
+            provider.build_http_header().get("Authorization"),
+            Some(&"Bearer abc".to_string())
+        );
+    }
+
+    #[test]
+    fn test_skus_without_bearer_token_fail() {
+        let provider = TCGPlayerProvider::with_bearer_token(None, "v1.39.0".to_string());
+        assert!(provider.build_http_header().get("Authorization").is_none());
+
+        let cards = vec![card("uuid-a", Some("1001"), None)];
+        let result = block_on_shared(provider.build_tcgplayer_skus_async(&cards));
+        assert!(matches!(result, Err(ProviderError::AuthError(_))));
+    }
+}
@@ -10,6 +10,7 @@ use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, RwLock};
 use std::time::{Duration, Instant};
 
+use crate::builders::runtime::block_on_shared;
 use crate::classes::base::JsonObject;
 use crate::classes::card::MtgjsonCardObject;
 use crate::classes::prices::MtgjsonPricesObject;
@@ -139,24 +140,12 @@ impl WhatsInStandardProvider {
             }
         });
 
-        let rt = tokio::runtime::Builder::new_current_thread()
-            .enable_all()
-            .build()
-            .map_err(|e| {
-                PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
-                    "Failed to create async runtime: {}",
-                    e
-                ))
-            })?;
-
-        let sets = rt
-            .block_on(async { self.standard_legal_set_codes_async().await })
-            .map_err(|e| {
-                PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
-                    "Standard sets fetch error: {}",
-                    e
-                ))
-            })?;
+        let sets = block_on_shared(self.standard_legal_set_codes_async()).map_err(|e| {
+            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
+                "Standard sets fetch error: {}",
+                e
+            ))
+        })?;
 
         {
             let mut cache = STANDARD_SETS_CACHE.write().map_err(|e| {
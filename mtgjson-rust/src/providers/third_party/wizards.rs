@@ -1,3 +1,4 @@
+use crate::builders::runtime::block_on_shared;
 use crate::prices::MtgjsonPricesObject;
 use crate::providers::{AbstractProvider, BaseProvider, ProviderError, ProviderResult};
 use async_trait::async_trait;
@@ -43,15 +44,9 @@ impl WizardsProvider {
             return Ok(self.magic_rules.clone());
         }
 
-        let runtime = tokio::runtime::Runtime::new()?;
-        runtime
-            .block_on(async { self.get_magic_rules_async().await })
-            .map_err(|e| {
-                PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
-                    "Magic rules error: {}",
-                    e
-                ))
-            })
+        block_on_shared(async { self.get_magic_rules_async().await }).map_err(|e| {
+            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Magic rules error: {}", e))
+        })
     }
 }
 
@@ -3,6 +3,7 @@ MTGJSON Constants that cannot be changed and are hardcoded intentionally
 """
 import datetime
 import hashlib
+import json
 import os
 import pathlib
 from typing import Dict, Set
@@ -152,4 +153,5 @@ SUPPORTED_SET_TYPES: Set[str] = {
     "commander",
     "masters",
 }
-MULTI_WORD_SUB_TYPES: Set[str] = {"Time Lord"}
+with RESOURCE_PATH.joinpath("multi_word_sub_types.json").open(encoding="utf-8") as f:
+    MULTI_WORD_SUB_TYPES: Set[str] = set(json.load(f))